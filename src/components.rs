@@ -1,11 +1,121 @@
+use crate::constants::{
+    COLOR_BACKUP_BODY, COLOR_BULLET, COLOR_BUNGEE_BODY, COLOR_DANCER_BODY, COLOR_DIGGER_BODY,
+    COLOR_FIRE_PEA, COLOR_FOOTBALL_BODY, COLOR_FROZEN_PEA, COLOR_GARGANTUAR_BODY, COLOR_IMP_BODY,
+    COLOR_SPIKE, COLOR_SPORE, COLOR_STAR, COLOR_ZOMBIE_ARM, COLOR_ZOMBIE_BODY, ZOMBIE_EAT_DPS,
+    ZOMBIE_SPEED,
+};
+use crate::events::Delivery;
 use bevy::prelude::*;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PlantType {
     Peashooter,
     Sunflower,
     WallNut,
     PotatoMine,
+    CabbagePult,
+    KernelPult,
+    Spikeweed,
+    SnowPea,
+    Torchwood,
+    TallNut,
+    Pumpkin,
+    LilyPad,
+    Squash,
+    PuffShroom,
+    SunShroom,
+    FumeShroom,
+    ScaredyShroom,
+    CoffeeBean,
+    Starfruit,
+    Cactus,
+    Repeater,
+    MelonPult,
+    GatlingPea,
+    TwinSunflower,
+    WinterMelon,
+    MagnetShroom,
+    Garlic,
+    Blover,
+    GraveBuster,
+    SplitPea,
+    UmbrellaLeaf,
+}
+
+// Slots a single cell can hold, from the ground up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PlantLayer {
+    Base,  // Lily Pad, holds a plant on water
+    Main,  // Regular plants
+    Shell, // Pumpkin, wraps the plant and absorbs bites first
+    Top,   // Coffee Bean, sits on a sleeping mushroom
+}
+
+impl PlantLayer {
+    // Outer layers draw in front of inner ones
+    pub const fn z(self) -> f32 {
+        match self {
+            Self::Base => 0.5,
+            Self::Main => 1.0,
+            Self::Shell => 1.5,
+            Self::Top => 1.9,
+        }
+    }
+}
+
+impl PlantType {
+    // Ground traps are walked over instead of eaten
+    pub const fn is_edible(self) -> bool {
+        !matches!(self, Self::Spikeweed | Self::CoffeeBean)
+    }
+
+    pub const fn layer(self) -> PlantLayer {
+        match self {
+            Self::LilyPad => PlantLayer::Base,
+            Self::Pumpkin => PlantLayer::Shell,
+            Self::CoffeeBean => PlantLayer::Top,
+            _ => PlantLayer::Main,
+        }
+    }
+
+    // Upgrades can only be planted onto their base plant
+    pub const fn upgrade_base(self) -> Option<Self> {
+        match self {
+            Self::GatlingPea => Some(Self::Repeater),
+            Self::TwinSunflower => Some(Self::Sunflower),
+            Self::WinterMelon => Some(Self::MelonPult),
+            _ => None,
+        }
+    }
+
+    // Seconds before the seed packet can be used again
+    pub const fn recharge(self) -> f32 {
+        if self.upgrade_base().is_some() {
+            50.0
+        } else {
+            0.0
+        }
+    }
+
+    // Mushrooms sleep through the day unless woken by a Coffee Bean
+    pub const fn is_mushroom(self) -> bool {
+        matches!(
+            self,
+            Self::PuffShroom
+                | Self::SunShroom
+                | Self::FumeShroom
+                | Self::ScaredyShroom
+                | Self::MagnetShroom
+        )
+    }
+
+    // Pea shooters squash back a little on every shot
+    pub const fn is_pea_shooter(self) -> bool {
+        matches!(
+            self,
+            Self::Peashooter | Self::SnowPea | Self::Repeater | Self::GatlingPea | Self::SplitPea
+        )
+    }
 }
 
 #[derive(Component)]
@@ -14,24 +124,561 @@ pub struct Plant {
     pub timer: Timer,
     pub health: f32,
 
+    pub asleep: bool, // Skips all actions, see `PlantType::is_mushroom`
+    pub age: f32,     // Seconds spent awake
+
     // Potato Mine specific
     pub armed: bool,
 }
 
+// Bite flash, shot recoil and damage stage of a planted plant, see `plant_visual_system`
+#[derive(Component)]
+pub struct PlantAnimation {
+    pub clock: f32,
+    pub max_health: f32, // Health it was planted with, damage stages are fractions of it
+    pub last_health: f32, // Compared each frame to notice bites
+    pub bite: f32,       // Seconds until the next bite can flash again
+    pub recoil: f32,     // Seconds left of the squash after a shot
+}
+
+// Plant sprites that change with the plant's state
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlantPart {
+    MineBody,  // Pops up out of the ground once armed
+    MineLight, // Blinks once armed
+    Crack(u8), // Shown from this damage stage on
+    Flash,     // White overlay shown while bitten
+}
+
+// Bar over a plant or zombie, shown once it's damaged if `Settings::show_health` is on
+#[derive(Component)]
+pub struct HealthBar {
+    pub fill: Entity, // Child sprite scaled to the health left
+}
+
+// Floating number over a hit zombie, hits landing close together add up on the same one
+#[derive(Component)]
+pub struct DamageNumber {
+    pub zombie: Entity,
+    pub amount: f32,
+    pub timer: Timer,
+}
+
+// Dims a seed packet while it recharges
+#[derive(Component)]
+pub struct RechargeOverlay(pub PlantType);
+
+// "Zzz" child sprite over a sleeping plant
+#[derive(Component)]
+pub struct SleepIndicator;
+
+// Where a zombie is, and so what can reach it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetLayer {
+    Ground,
+    Underground, // Only explosions get through
+    Air,         // Over the plants, only spikes and explosions get up there
+}
+
 #[derive(PartialEq, Eq)]
 pub enum ZombieState {
     Walking,
-    Eating(Entity), // Entity being eaten
+    Eating(Entity),    // Entity being eaten
+    ChangingLane(i32), // Walking diagonally into this row, after biting Garlic
+    Vaulting,          // Mid-air over a plant, see `Vault`
+    Dancing,           // Standing still while summoning Backup Dancers
+    Hanging,           // On a Bungee cord, see `Bungee`
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZombieKind {
+    Basic,
+    Conehead,
+    Buckethead,
+    ScreenDoor,
+    Football,
+    Ladder,
+    PogoStick,
+    PoleVaulting,
+    Newspaper,
+    Dancing,
+    BackupDancer,
+    Digger,
+    Balloon,
+    Gargantuar,
+    Imp,
+    Bungee,
+    Zomboni,
+}
+
+// The one trick a kind has on top of walking and biting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZombieAbility {
+    None,
+    Vault,    // Runs with a pole and jumps the first plant it meets
+    Dance,    // Stops on the lawn to summon Backup Dancers around it
+    ThrowImp, // Carries an Imp and throws it ahead at half health
+    IceTrail, // Drives an ice machine, leaves ice behind and is wrecked by Spikeweed
+    Drop,     // Comes down from the sky onto a plant instead of walking in, see `Bungee`
+}
+
+// What `spawn_zombie_visuals` builds for a kind
+#[derive(Clone, Copy)]
+pub enum ZombieLook {
+    Walker { body: Color, arm: Color }, // Legs, body, head and an arm that can fall off
+    Vehicle,                            // Zomboni's ice machine with its driver on top
+}
+
+// Everything the spawner needs to know about a zombie kind
+pub struct ZombieStats {
+    pub health: f32,
+    pub equipment: Option<EquipmentKind>,
+    pub speed: f32,      // Walking, pixels per second
+    pub walk_speed: f32, // Once its pole, pogo stick or tunnel is spent
+    pub bite_dps: f32,   // Damage per second to the plant being eaten
+    pub cost: u32,       // Wave points, see `spawn_zombies`
+    pub look: ZombieLook,
+    pub scale: f32, // Drawing size relative to a basic zombie
+
+    // Special behaviour flags
+    pub ability: ZombieAbility,
+    pub smashes: bool, // Flattens or crushes the plant it reaches instead of eating it
+    // Where it enters: Underground tunnels to the house side and walks back, Air floats over
+    // plants until the balloon is popped
+    pub layer: TargetLayer,
+}
+
+impl ZombieKind {
+    // Kinds the wave spawner picks from, Backup Dancers and Imps only come with another zombie
+    pub const ALL: [Self; 15] = [
+        Self::Basic,
+        Self::Conehead,
+        Self::Buckethead,
+        Self::ScreenDoor,
+        Self::Football,
+        Self::Ladder,
+        Self::PogoStick,
+        Self::PoleVaulting,
+        Self::Newspaper,
+        Self::Dancing,
+        Self::Digger,
+        Self::Balloon,
+        Self::Gargantuar,
+        Self::Bungee,
+        Self::Zomboni,
+    ];
+
+    pub const fn stats(self) -> ZombieStats {
+        let basic = ZombieStats {
+            health: 100.0,
+            equipment: None,
+            speed: ZOMBIE_SPEED,
+            walk_speed: ZOMBIE_SPEED,
+            bite_dps: ZOMBIE_EAT_DPS,
+            cost: 1,
+            look: ZombieLook::Walker {
+                body: COLOR_ZOMBIE_BODY,
+                arm: COLOR_ZOMBIE_ARM,
+            },
+            scale: 1.0,
+            ability: ZombieAbility::None,
+            smashes: false,
+            layer: TargetLayer::Ground,
+        };
+        match self {
+            Self::Basic => basic,
+            Self::BackupDancer => ZombieStats {
+                look: Self::jersey(COLOR_BACKUP_BODY),
+                ..basic
+            },
+            Self::Conehead => ZombieStats {
+                equipment: Some(EquipmentKind::Cone),
+                cost: 2,
+                ..basic
+            },
+            Self::Buckethead => ZombieStats {
+                equipment: Some(EquipmentKind::Bucket),
+                cost: 4,
+                ..basic
+            },
+            Self::ScreenDoor => ZombieStats {
+                equipment: Some(EquipmentKind::ScreenDoor),
+                cost: 4,
+                ..basic
+            },
+            Self::Football => ZombieStats {
+                equipment: Some(EquipmentKind::FootballHelmet),
+                speed: ZOMBIE_SPEED * 2.0,
+                cost: 7,
+                look: Self::jersey(COLOR_FOOTBALL_BODY),
+                ..basic
+            },
+            Self::Ladder => ZombieStats {
+                equipment: Some(EquipmentKind::Ladder),
+                cost: 4,
+                ..basic
+            },
+            Self::PogoStick => ZombieStats {
+                equipment: Some(EquipmentKind::PogoStick),
+                speed: ZOMBIE_SPEED * 1.5,
+                cost: 4,
+                ..basic
+            },
+            // Runs until the vault, walks at normal speed after it
+            Self::PoleVaulting => ZombieStats {
+                speed: ZOMBIE_SPEED * 2.0,
+                cost: 3,
+                ability: ZombieAbility::Vault,
+                ..basic
+            },
+            Self::Newspaper => ZombieStats {
+                equipment: Some(EquipmentKind::Newspaper),
+                cost: 2,
+                ..basic
+            },
+            // Fast underground, walks at the basic speed once up
+            Self::Digger => ZombieStats {
+                speed: ZOMBIE_SPEED * 1.5,
+                cost: 4,
+                look: Self::jersey(COLOR_DIGGER_BODY),
+                layer: TargetLayer::Underground,
+                ..basic
+            },
+            // Boss: slow, huge health, throws the Imp off its back at half of it
+            Self::Gargantuar => ZombieStats {
+                health: 3000.0,
+                speed: ZOMBIE_SPEED * 0.7,
+                cost: 10,
+                look: Self::jersey(COLOR_GARGANTUAR_BODY),
+                scale: 1.6,
+                ability: ZombieAbility::ThrowImp,
+                smashes: true,
+                ..basic
+            },
+            Self::Imp => ZombieStats {
+                health: 60.0,
+                speed: ZOMBIE_SPEED * 1.5,
+                look: Self::jersey(COLOR_IMP_BODY),
+                scale: 0.6,
+                ..basic
+            },
+            // Vehicle: crushes plants and leaves ice behind, see `ice_trail_system`
+            Self::Zomboni => ZombieStats {
+                health: 1350.0,
+                speed: ZOMBIE_SPEED * 1.2,
+                cost: 7,
+                look: ZombieLook::Vehicle,
+                scale: 1.6,
+                ability: ZombieAbility::IceTrail,
+                smashes: true,
+                ..basic
+            },
+            // Drops onto a plant from above instead of walking in, see `Bungee`
+            Self::Bungee => ZombieStats {
+                speed: 0.0,
+                cost: 3,
+                look: Self::jersey(COLOR_BUNGEE_BODY),
+                ability: ZombieAbility::Drop,
+                layer: TargetLayer::Air,
+                ..basic
+            },
+            Self::Balloon => ZombieStats {
+                equipment: Some(EquipmentKind::Balloon),
+                cost: 3,
+                layer: TargetLayer::Air,
+                ..basic
+            },
+            Self::Dancing => ZombieStats {
+                health: 150.0,
+                cost: 5,
+                look: Self::jersey(COLOR_DANCER_BODY),
+                ability: ZombieAbility::Dance,
+                ..basic
+            },
+        }
+    }
+
+    // Outfit that covers the arms too
+    const fn jersey(color: Color) -> ZombieLook {
+        ZombieLook::Walker {
+            body: color,
+            arm: color,
+        }
+    }
 }
 
 #[derive(Component)]
 pub struct Zombie {
+    pub kind: ZombieKind,
     pub health: f32,
+    pub speed: f32,     // Current walking speed, starts at the kind's
+    pub direction: f32, // -1 walks left towards the house, 1 walks back to the right
+    pub layer: TargetLayer,
     pub state: ZombieState,
+    pub row: i32,
+}
+
+// What an item is made of, Magnet-shroom only pulls metal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EquipmentMaterial {
+    Plastic,
+    Metal,
+    Paper,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EquipmentKind {
+    Cone,
+    Bucket,
+    ScreenDoor,
+    FootballHelmet,
+    Ladder,
+    PogoStick,
+    Newspaper,
+    Balloon,
+}
+
+impl EquipmentKind {
+    pub const fn material(self) -> EquipmentMaterial {
+        match self {
+            Self::Cone | Self::Balloon => EquipmentMaterial::Plastic,
+            Self::Newspaper => EquipmentMaterial::Paper,
+            _ => EquipmentMaterial::Metal,
+        }
+    }
+
+    // Damage the item soaks up before the body, Ladders and Pogo Sticks don't protect
+    pub const fn armor(self) -> f32 {
+        match self {
+            Self::Cone => 140.0,
+            Self::Bucket | Self::ScreenDoor => 400.0,
+            Self::FootballHelmet => 500.0,
+            Self::Newspaper => 60.0,
+            Self::Balloon => 20.0,
+            Self::Ladder | Self::PogoStick => 0.0,
+        }
+    }
+
+    // Head armor takes every hit, shields only the ones that reach them
+    pub fn blocks(self, delivery: Delivery, head_on: bool) -> bool {
+        match self {
+            // Held in front, so the door or paper only covers straight hits coming at the zombie's face
+            Self::ScreenDoor | Self::Newspaper => delivery == Delivery::Straight && head_on,
+            // Floats above the zombie and takes every hit except explosions
+            Self::Balloon => delivery != Delivery::Area,
+            Self::Cone | Self::Bucket | Self::FootballHelmet => true,
+            Self::Ladder | Self::PogoStick => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PartKind {
+    Leg(f32), // Swing direction, the two legs are opposite
+    Body,
+    Head,
+    Arm,
+}
+
+// Body part sprite of a zombie, posed relative to where it was spawned
+#[derive(Component)]
+pub struct ZombiePart {
+    pub kind: PartKind,
+    pub base: Vec3,   // Resting position on the zombie
+    pub color: Color, // Restored after a hit flash
+}
+
+// Animation clock and hit flash of a living zombie
+#[derive(Component, Default)]
+pub struct ZombieAnimation {
+    pub clock: f32,
+    pub flash: f32, // Seconds left showing white after a hit
+    pub arm_dropped: bool,
+}
+
+// Killed zombie falling over, no longer a `Zombie` to anything else
+#[derive(Component)]
+pub struct Dying {
+    pub timer: Timer,
+    pub direction: f32, // Walking direction it had, it falls backwards
+}
+
+// Removable item carried by a zombie, a child sprite that can be pulled off
+#[derive(Component)]
+pub struct Equipment {
+    pub kind: EquipmentKind,
+    pub health: f32, // Armor left, see `EquipmentKind::armor`
+}
+
+// Plant with a Ladder zombie's ladder against it, every zombie reaching it climbs over
+#[derive(Component)]
+pub struct Laddered;
+
+// Digging under the lawn: can't be shot, lobbed at or bitten into, only explosions reach it
+#[derive(Component)]
+pub struct Tunneling {
+    pub mound: Entity, // Dirt child sprite shown instead of the zombie
+}
+
+// Imp riding on a Gargantuar's back until it's thrown
+#[derive(Component)]
+pub struct ImpCarrier {
+    pub imp: Option<Entity>, // Child sprite, taken off once the Imp is thrown
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BungeePhase {
+    Marking,  // Target shown on the cell, still up in the sky
+    Dropping, // On the way down
+    Grabbing, // On the lawn, reaching for the plant
+    Rising,   // Back up, with the plant if it got one
+}
+
+// Bungee zombie working a single cell from above
+#[derive(Component)]
+pub struct Bungee {
+    pub col: i32,
+    pub phase: BungeePhase,
+    pub timer: Timer,   // Time left in the current phase
+    pub marker: Entity, // Target child sprite, kept on the cell while the zombie moves
+    pub sky_y: f32,     // Where it hangs before dropping and goes back to
+}
+
+// Pole still in hand, used up by the first plant the zombie reaches
+#[derive(Component)]
+pub struct Pole {
+    pub sprite: Entity, // Child sprite carried by the zombie
+}
+
+// Calls Backup Dancers into the free slots around it once on the lawn, and again whenever
+// some of them are gone
+#[derive(Component)]
+pub struct Dancer {
+    pub entered: bool, // Reached the lawn, summons from then on
+    pub pause: Timer,  // Time spent dancing in place before the crew shows up
+    pub resummon: Timer,
+}
+
+// Backup Dancer and the slot it fills around its leader, see `BACKUP_SLOTS`
+#[derive(Component)]
+pub struct Backup {
+    pub leader: Entity,
+    pub slot: usize,
+}
+
+// Pole vault over a plant, landing behind it when the timer ends
+#[derive(Component)]
+pub struct Vault {
+    pub start: Vec2,
+    pub target: Vec2,
+    pub height: f32, // Peak of the arc above the ground line
+    pub timer: Timer,
+}
+
+// Immobilized by Kernel-pult butter (no walking, no eating)
+#[derive(Component)]
+pub struct Buttered {
+    pub timer: Timer,
+    pub splat: Entity, // Child sprite on the zombie's head
+}
+
+// Slowed by frozen peas (half speed, half bite rate)
+#[derive(Component)]
+pub struct Chilled {
+    pub timer: Timer,
+    pub tint: Entity, // Icy overlay child sprite
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulletKind {
+    Pea,
+    FrozenPea,
+    FirePea,
+    Spore, // Puff-shroom and Scaredy-shroom shots
+    Star,
+    Spike, // Cactus, long and thin
+}
+
+impl BulletKind {
+    pub const fn color(self) -> Color {
+        match self {
+            Self::Pea => COLOR_BULLET,
+            Self::FrozenPea => COLOR_FROZEN_PEA,
+            Self::FirePea => COLOR_FIRE_PEA,
+            Self::Spore => COLOR_SPORE,
+            Self::Star => COLOR_STAR,
+            Self::Spike => COLOR_SPIKE,
+        }
+    }
+
+    pub const fn size(self) -> Vec2 {
+        match self {
+            Self::Spike => Vec2::new(20.0, 6.0),
+            _ => Vec2::splat(15.0),
+        }
+    }
+
+    // Spikes are the only shots that reach the air
+    pub fn hits(self, layer: TargetLayer) -> bool {
+        match layer {
+            TargetLayer::Ground => true,
+            TargetLayer::Air => self == Self::Spike,
+            TargetLayer::Underground => false,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Bullet {
+    pub kind: BulletKind,
+    pub velocity: Vec2,
+    pub damage: f32,
+    pub torchwood: Option<Entity>, // Last Torchwood passed, each stump only converts once
+    pub range: f32,                // Distance left before it fizzles out
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LobbedKind {
+    Cabbage,
+    Kernel,
+    Butter,
+    Melon,
+    WinterMelon, // Chills everything it splashes
+}
+
+impl LobbedKind {
+    pub const fn damage(self) -> f32 {
+        match self {
+            Self::Cabbage | Self::Butter => 20.0,
+            Self::Kernel => 10.0,
+            Self::Melon | Self::WinterMelon => 40.0,
+        }
+    }
+
+    // Melons splash a third of their damage onto nearby zombies
+    pub const fn splashes(self) -> bool {
+        matches!(self, Self::Melon | Self::WinterMelon)
+    }
+}
+
+// Arcing projectile: flies over everything and only hits on landing
 #[derive(Component)]
-pub struct Bullet;
+pub struct Lobbed {
+    pub kind: LobbedKind,
+    pub start: Vec2,
+    pub target: Vec2,
+    pub row: i32,
+    pub timer: Timer, // Flight time
+}
+
+// Squash in mid-air, crushing the landing cell when the timer ends
+#[derive(Component)]
+pub struct SquashLeap {
+    pub row: i32, // Off the grid while in the air, so it keeps its lane here
+    pub start: Vec2,
+    pub target: Vec2, // Center of the landing cell
+    pub timer: Timer,
+}
 
 #[derive(Component)]
 pub struct GridCell {
@@ -68,3 +715,15 @@ pub struct Particle {
 
 #[derive(Component)]
 pub struct Cursor;
+
+// Hides the right side of the lawn at night until a Blover blows it away
+#[derive(Component)]
+pub struct Fog;
+
+// Ice left behind by a Zomboni, gone once its cell's time in `Lawn::ice` runs out
+#[derive(Component)]
+pub struct IceTrail;
+
+// Headstone blocking its cell, only a Grave Buster can be planted on it
+#[derive(Component)]
+pub struct Grave;
@@ -3,12 +3,41 @@ use bevy::prelude::*;
 pub const TILE_SIZE: f32 = 80.0;
 pub const ROWS: i32 = 5;
 pub const COLS: i32 = 9;
-pub const SCREEN_WIDTH: f32 = COLS as f32 * TILE_SIZE + 200.0; // Extra width for HUD
+pub const SCREEN_WIDTH: f32 = COLS as f32 * TILE_SIZE + 300.0; // Extra width for HUD
 pub const SCREEN_HEIGHT: f32 = ROWS as f32 * TILE_SIZE + 100.0;
 
+// Grid geometry (center of cell 0,0)
+pub const GRID_START_X: f32 = -SCREEN_WIDTH / 2.0 + TILE_SIZE / 2.0 + 50.0; // Left align with margin
+pub const GRID_START_Y: f32 = -(ROWS as f32 * TILE_SIZE) / 2.0 + TILE_SIZE / 2.0;
+
+// Depth sorting: every row gets its own band of z, rows further down the screen draw on top
+pub const ROW_DEPTH: f32 = 10.0;
+pub const GROUND_DEPTH: f32 = 1.0; // Lawn, night shade and ice trails stay under every row
+pub const OVERLAY_DEPTH: f32 = GROUND_DEPTH + ROWS as f32 * ROW_DEPTH; // Fog stays over every row
+
+// Offsets inside a row's band, leaving room for each entity's child sprites
+pub const GRAVE_DEPTH: f32 = 0.0;
+pub const PLANT_DEPTH: f32 = 1.0; // Plus the plant's layer
+pub const ZOMBIE_DEPTH: f32 = 4.5;
+pub const ZOMBIE_DEPTH_SPREAD: f32 = 2.0; // Zombies further left draw over the ones behind them
+pub const PROJECTILE_DEPTH: f32 = 8.5;
+pub const DAMAGE_NUMBER_DEPTH: f32 = OVERLAY_DEPTH - 0.5; // Top of the front row, under the fog
+
+// Seed packet HUD
+pub const SEED_PACKET_COLUMNS: usize = 4;
+pub const SEED_PACKET_WIDTH: f32 = 56.0;
+pub const SEED_PACKET_HEIGHT: f32 = 44.0;
+pub const SEED_PACKET_GAP: f32 = 4.0;
+
 // Colors
 pub const COLOR_GRASS_1: Color = Color::rgb(0.0, 0.4, 0.0);
 pub const COLOR_GRASS_2: Color = Color::rgb(0.0, 0.35, 0.0);
+pub const COLOR_WATER_1: Color = Color::rgb(0.1, 0.35, 0.7);
+pub const COLOR_WATER_2: Color = Color::rgb(0.1, 0.3, 0.65);
+pub const COLOR_NIGHT_SHADE: Color = Color::rgba(0.0, 0.0, 0.15, 0.45);
+pub const COLOR_FOG: Color = Color::rgba(0.75, 0.75, 0.85, 0.85);
+pub const COLOR_GRAVE: Color = Color::rgb(0.45, 0.45, 0.5);
+pub const COLOR_SLEEP_INDICATOR: Color = Color::rgb(0.3, 0.3, 0.8);
 pub const COLOR_SUN_TEXT: Color = Color::WHITE;
 // pub const COLOR_SELECTED_TEXT: Color = Color::GOLD; // Unused in main.rs but was there
 
@@ -26,21 +55,211 @@ pub const COLOR_WALLNUT_FACE: Color = Color::BLACK;
 
 pub const COLOR_POTATOMINE_BODY: Color = Color::rgb(0.5, 0.4, 0.3);
 pub const COLOR_POTATOMINE_ARMED: Color = Color::RED;
+pub const COLOR_WALLNUT_CRACK: Color = Color::rgb(0.3, 0.18, 0.08);
+pub const COLOR_HEALTH_BAR_BACK: Color = Color::rgba(0.1, 0.1, 0.1, 0.8);
+pub const COLOR_HEALTH_BAR_FILL: Color = Color::rgb(0.2, 0.85, 0.2);
+pub const COLOR_DAMAGE_NUMBER: Color = Color::rgb(1.0, 0.95, 0.6);
+pub const COLOR_PLANT_FLASH: Color = Color::rgba(1.0, 1.0, 1.0, 0.6);
+
+pub const COLOR_PULT_BASE: Color = Color::rgb(0.3, 0.5, 0.1);
+pub const COLOR_PULT_ARM: Color = Color::rgb(0.4, 0.3, 0.1);
+pub const COLOR_CABBAGE: Color = Color::rgb(0.5, 0.9, 0.4);
+pub const COLOR_KERNEL: Color = Color::rgb(1.0, 0.85, 0.3);
+pub const COLOR_BUTTER: Color = Color::rgb(1.0, 0.95, 0.5);
+
+pub const COLOR_SPIKEWEED_BASE: Color = Color::rgb(0.2, 0.45, 0.1);
+pub const COLOR_SPIKEWEED_SPIKE: Color = Color::rgb(0.85, 0.85, 0.8);
+
+pub const COLOR_SNOWPEA_HEAD: Color = Color::rgb(0.5, 0.75, 1.0);
+pub const COLOR_SNOWPEA_SNOUT: Color = Color::rgb(0.35, 0.6, 0.9);
+
+pub const COLOR_TORCHWOOD_STUMP: Color = Color::rgb(0.45, 0.25, 0.1);
+pub const COLOR_TORCHWOOD_FIRE: Color = Color::rgb(1.0, 0.45, 0.0);
+
+pub const COLOR_TALLNUT_BODY: Color = Color::rgb(0.55, 0.35, 0.15);
+
+pub const COLOR_PUMPKIN: Color = Color::rgb(1.0, 0.55, 0.1);
+
+pub const COLOR_LILYPAD: Color = Color::rgb(0.2, 0.6, 0.2);
+
+pub const COLOR_SQUASH_BODY: Color = Color::rgb(0.45, 0.65, 0.25);
+pub const COLOR_SQUASH_FACE: Color = Color::rgb(0.1, 0.2, 0.05);
+
+pub const COLOR_MUSHROOM_STEM: Color = Color::rgb(0.9, 0.85, 0.75);
+pub const COLOR_PUFFSHROOM_CAP: Color = Color::rgb(0.6, 0.3, 0.7);
+pub const COLOR_SUNSHROOM_CAP: Color = Color::rgb(0.95, 0.7, 0.2);
+pub const COLOR_FUMESHROOM_CAP: Color = Color::rgb(0.5, 0.25, 0.6);
+pub const COLOR_SCAREDYSHROOM_CAP: Color = Color::rgb(0.75, 0.5, 0.8);
+pub const COLOR_COFFEEBEAN: Color = Color::rgb(0.35, 0.2, 0.1);
+pub const COLOR_SPORE: Color = Color::rgb(0.8, 0.5, 0.9);
+pub const COLOR_FUME: Color = Color::rgba(0.7, 0.4, 0.8, 0.5);
+
+pub const COLOR_STARFRUIT: Color = Color::rgb(1.0, 0.85, 0.1);
+pub const COLOR_STAR: Color = Color::rgb(1.0, 0.9, 0.3);
+
+pub const COLOR_CACTUS: Color = Color::rgb(0.3, 0.6, 0.25);
+pub const COLOR_SPIKE: Color = Color::rgb(0.9, 0.9, 0.7);
+
+pub const COLOR_REPEATER_HEAD: Color = Color::rgb(0.15, 0.65, 0.15);
+pub const COLOR_GATLING_HEAD: Color = Color::rgb(0.1, 0.55, 0.1);
+pub const COLOR_GATLING_HELMET: Color = Color::rgb(0.15, 0.3, 0.1);
+pub const COLOR_MELON: Color = Color::rgb(0.3, 0.7, 0.3);
+pub const COLOR_WINTER_MELON: Color = Color::rgb(0.6, 0.85, 1.0);
+pub const COLOR_RECHARGE_OVERLAY: Color = Color::rgba(0.0, 0.0, 0.0, 0.6);
+pub const COLOR_MAGNETSHROOM_CAP: Color = Color::rgb(0.45, 0.3, 0.55);
+pub const COLOR_MAGNET: Color = Color::rgb(0.85, 0.15, 0.15);
+pub const COLOR_GARLIC: Color = Color::rgb(0.95, 0.95, 0.85);
+pub const COLOR_GARLIC_TIP: Color = Color::rgb(0.7, 0.75, 0.5);
+pub const COLOR_BLOVER: Color = Color::rgb(0.3, 0.85, 0.35);
+pub const COLOR_UMBRELLA_LEAF: Color = Color::rgb(0.35, 0.7, 0.35);
+pub const COLOR_SPLITPEA_HEAD: Color = Color::rgb(0.3, 0.75, 0.25);
+pub const COLOR_GRAVE_BUSTER: Color = Color::rgb(0.35, 0.55, 0.3);
 
 // Zombie Colors
 pub const COLOR_ZOMBIE_LEGS: Color = Color::rgb(0.2, 0.2, 0.2);
 pub const COLOR_ZOMBIE_BODY: Color = Color::rgb(0.2, 0.2, 0.6);
 pub const COLOR_ZOMBIE_HEAD: Color = Color::rgb(0.6, 0.7, 0.6);
 pub const COLOR_ZOMBIE_ARM: Color = Color::rgb(0.2, 0.2, 0.6);
+pub const COLOR_FOOTBALL_BODY: Color = Color::rgb(0.6, 0.1, 0.1);
+pub const COLOR_NEWSPAPER: Color = Color::rgb(0.9, 0.9, 0.85);
+pub const COLOR_DANCER_BODY: Color = Color::rgb(0.55, 0.2, 0.6);
+pub const COLOR_BACKUP_BODY: Color = Color::rgb(0.4, 0.25, 0.5);
+pub const COLOR_GARGANTUAR_BODY: Color = Color::rgb(0.35, 0.3, 0.25);
+pub const COLOR_ZOMBONI: Color = Color::rgb(0.3, 0.45, 0.7);
+pub const COLOR_ZOMBONI_ROLLER: Color = Color::rgb(0.6, 0.6, 0.65);
+pub const COLOR_ICE: Color = Color::rgba(0.8, 0.92, 1.0, 0.7);
+pub const COLOR_BUNGEE_BODY: Color = Color::rgb(0.5, 0.55, 0.45);
+pub const COLOR_BUNGEE_CORD: Color = Color::rgb(0.85, 0.85, 0.8);
+pub const COLOR_BUNGEE_TARGET: Color = Color::rgba(0.9, 0.1, 0.1, 0.6);
+pub const COLOR_IMP_BODY: Color = Color::rgb(0.7, 0.55, 0.4);
+pub const COLOR_DIGGER_BODY: Color = Color::rgb(0.45, 0.35, 0.2);
+pub const COLOR_DIRT_MOUND: Color = Color::rgb(0.4, 0.25, 0.1);
+pub const COLOR_BALLOON: Color = Color::rgb(0.9, 0.2, 0.25);
+pub const COLOR_POLE: Color = Color::rgb(0.55, 0.4, 0.2);
+
+// Equipment Colors
+pub const COLOR_CONE: Color = Color::rgb(1.0, 0.55, 0.1);
+pub const COLOR_BUCKET: Color = Color::rgb(0.6, 0.6, 0.65);
+pub const COLOR_SCREEN_DOOR: Color = Color::rgb(0.45, 0.45, 0.5);
+pub const COLOR_FOOTBALL_HELMET: Color = Color::rgb(0.7, 0.1, 0.1);
+pub const COLOR_LADDER: Color = Color::rgb(0.7, 0.7, 0.75);
+pub const COLOR_POGO: Color = Color::rgb(0.5, 0.5, 0.55);
 
 pub const COLOR_BULLET: Color = Color::rgb(0.0, 1.0, 1.0);
+pub const COLOR_FROZEN_PEA: Color = Color::rgb(0.6, 0.8, 1.0);
+pub const COLOR_FIRE_PEA: Color = Color::rgb(1.0, 0.5, 0.0);
+pub const COLOR_CHILLED: Color = Color::rgba(0.5, 0.7, 1.0, 0.4);
 
 pub const ZOMBIE_SPEED: f32 = 20.0;
 pub const BULLET_SPEED: f32 = 200.0;
 pub const ZOMBIE_EAT_DPS: f32 = 20.0; // Damage per second when eating
+pub const NEWSPAPER_RAGE_FACTOR: f32 = 2.5; // Speed boost once the paper is destroyed
+pub const DANCER_PAUSE_TIME: f32 = 1.5;
+pub const DANCER_RESUMMON_TIME: f32 = 8.0;
+// Backup Dancer offsets from the leader: lane above, lane below, ahead and behind in its own
+pub const BACKUP_SLOTS: [(f32, i32); 4] = [(0.0, 1), (0.0, -1), (-TILE_SIZE, 0), (TILE_SIZE, 0)];
+pub const VAULT_TIME: f32 = 0.8;
+pub const VAULT_HEIGHT: f32 = 90.0;
+pub const VAULT_DISTANCE: f32 = 0.75 * TILE_SIZE; // Landing spot behind the vaulted plant
+pub const POGO_HOP_HEIGHT: f32 = 50.0; // Lower than a vault, it hops every plant
+pub const IMP_THROW_DISTANCE: f32 = 3.0 * TILE_SIZE; // How far ahead the Gargantuar throws
+pub const IMP_THROW_TIME: f32 = 1.2;
+pub const IMP_THROW_HEIGHT: f32 = 160.0;
+pub const GARLIC_BITE_DAMAGE: f32 = 20.0; // The single bite taken before turning away
+
+// Lobbed projectiles
+pub const LOB_FLIGHT_TIME: f32 = 1.0; // Seconds from throw to landing
+pub const LOB_ARC_HEIGHT: f32 = 80.0;
+pub const BUTTER_CHANCE: f64 = 0.25; // Kernel-pult throws butter instead of a kernel
+pub const BUTTER_DURATION: f32 = 4.0;
+
+pub const PEA_DAMAGE: f32 = 10.0;
+pub const CHILL_DURATION: f32 = 10.0;
+pub const CHILL_SPEED_FACTOR: f32 = 0.5; // Applies to walking and biting
+pub const FIRE_SPLASH_RADIUS: f32 = 60.0;
+pub const MELON_SPLASH_RADIUS: f32 = 80.0;
+
+pub const SQUASH_LEAP_TIME: f32 = 0.6;
+pub const SQUASH_LEAP_HEIGHT: f32 = 70.0;
+pub const SQUASH_DAMAGE: f32 = 5000.0; // More than any zombie has, armor included
+
+pub const PUFFSHROOM_RANGE: f32 = 3.0 * TILE_SIZE;
+pub const FUMESHROOM_RANGE: f32 = 4.0 * TILE_SIZE;
+pub const FUME_DAMAGE: f32 = 10.0; // Every zombie in range
+pub const SCAREDY_FEAR_RADIUS: f32 = 1.5 * TILE_SIZE;
+pub const SUNSHROOM_GROW_TIME: f32 = 60.0;
+pub const MAGNET_RADIUS: f32 = 2.5 * TILE_SIZE;
+pub const MAGNET_HOLD_TIME: f32 = 15.0; // Held item blocks the next pull until it rusts away
+
+pub const FOG_COLUMNS: i32 = 4; // Counted from the right edge
+pub const FOG_CLEAR_TIME: f32 = 15.0; // Seconds before fog rolls back in after a Blover
+pub const GRAVE_COUNT: usize = 3;
+pub const GRAVE_BUSTER_TIME: f32 = 4.0;
+
+// Zombie animation
+pub const WALK_CYCLE_SPEED: f32 = 6.0; // Radians of leg swing phase per second
+pub const LEG_SWING_ANGLE: f32 = 0.35;
+pub const EAT_BOB_SPEED: f32 = 10.0;
+pub const EAT_BOB_HEIGHT: f32 = 5.0;
+pub const HIT_FLASH_TIME: f32 = 0.1;
+pub const DEATH_FALL_TIME: f32 = 0.8;
+
+// Health overlay
+pub const HEALTH_BAR_WIDTH: f32 = 40.0;
+pub const HEALTH_BAR_HEIGHT: f32 = 5.0;
+pub const DAMAGE_NUMBER_TIME: f32 = 0.8;
+pub const DAMAGE_NUMBER_MERGE_TIME: f32 = 0.25; // Hits on the same zombie within this share a number
+pub const DAMAGE_NUMBER_RISE: f32 = 40.0; // Pixels per second
+
+// Plant animation
+pub const PLANT_BITE_FLASH_INTERVAL: f32 = 0.5; // A plant being eaten flashes this often
+pub const PLANT_DAMAGE_STAGES: f32 = 3.0; // Wall-nut: whole, cracked, badly cracked
+pub const RECOIL_TIME: f32 = 0.15;
+pub const RECOIL_SQUASH: f32 = 0.2;
+pub const MINE_BLINK_SPEED: f32 = 8.0;
+
+pub const ICE_TRAIL_TIME: f32 = 30.0; // Seconds a Zomboni's ice keeps its cells unplantable
+
+// Bungee zombie phases, in order
+pub const BUNGEE_MARK_TIME: f32 = 3.0;
+pub const BUNGEE_DROP_TIME: f32 = 0.8;
+pub const BUNGEE_GRAB_TIME: f32 = 2.0;
+pub const BUNGEE_RISE_TIME: f32 = 1.0;
+
+// Starfruit: up, down, back and the two forward diagonals (degrees from +X)
+pub const STARFRUIT_ANGLES: [f32; 5] = [90.0, -90.0, 180.0, 30.0, -30.0];
+
+pub const SPIKEWEED_DAMAGE: f32 = 10.0; // Per zombie on the cell, every plant tick
 
 // Costs
 pub const COST_PEASHOOTER: u32 = 100;
 pub const COST_SUNFLOWER: u32 = 50;
 pub const COST_WALLNUT: u32 = 50;
 pub const COST_POTATOMINE: u32 = 25;
+pub const COST_CABBAGEPULT: u32 = 100;
+pub const COST_KERNELPULT: u32 = 100;
+pub const COST_SPIKEWEED: u32 = 100;
+pub const COST_SNOWPEA: u32 = 175;
+pub const COST_TORCHWOOD: u32 = 175;
+pub const COST_TALLNUT: u32 = 125;
+pub const COST_PUMPKIN: u32 = 125;
+pub const COST_LILYPAD: u32 = 25;
+pub const COST_SQUASH: u32 = 50;
+pub const COST_PUFFSHROOM: u32 = 0;
+pub const COST_SUNSHROOM: u32 = 25;
+pub const COST_FUMESHROOM: u32 = 75;
+pub const COST_SCAREDYSHROOM: u32 = 25;
+pub const COST_COFFEEBEAN: u32 = 75;
+pub const COST_STARFRUIT: u32 = 125;
+pub const COST_CACTUS: u32 = 125;
+pub const COST_MAGNETSHROOM: u32 = 100;
+pub const COST_GARLIC: u32 = 50;
+pub const COST_BLOVER: u32 = 100;
+pub const COST_GRAVEBUSTER: u32 = 75;
+pub const COST_SPLITPEA: u32 = 125;
+pub const COST_UMBRELLALEAF: u32 = 100;
+pub const COST_REPEATER: u32 = 200;
+pub const COST_MELONPULT: u32 = 300;
+pub const COST_GATLINGPEA: u32 = 250; // Upgrades, paid on top of the base plant
+pub const COST_TWINSUNFLOWER: u32 = 150;
+pub const COST_WINTERMELON: u32 = 200;
@@ -0,0 +1,29 @@
+use crate::components::ZombieKind;
+use bevy::prelude::*;
+
+// How a hit reaches the zombie, shields only stop some of them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    Straight, // Peas, spores, stars and spikes
+    Lobbed,   // Pult shots dropping from above
+    Area,     // Explosions, fumes, splashes and traps, nothing blocks these
+}
+
+// Zombie joining mid-level, spawned by `spawn_zombie_events`
+#[derive(Event)]
+pub struct SpawnZombieEvent {
+    pub kind: ZombieKind,
+    pub x: f32,
+    pub row: i32,
+    pub leader: Option<(Entity, usize)>, // Dancing zombie and backup slot it fills
+    pub thrown_to: Option<f32>,          // Landing x when it arrives flying, like a thrown Imp
+}
+
+// Damage dealt to a zombie, resolved through its shield and armor by `zombie_damage_system`
+#[derive(Event)]
+pub struct DamageEvent {
+    pub zombie: Entity,
+    pub amount: f32,
+    pub delivery: Delivery,
+    pub direction: Vec2, // Travel direction of the hit, zero for area damage
+}
@@ -3,22 +3,42 @@ use bevy::window::WindowResolution;
 
 mod components;
 mod constants;
+mod events;
 mod resources;
 mod systems;
 
 use constants::{SCREEN_HEIGHT, SCREEN_WIDTH};
-use resources::{GameState, SpawnTimer};
+use events::{DamageEvent, SpawnZombieEvent};
+use resources::{Director, GameState, Lawn, Level, Settings, SpawnTimer, LEVELS};
 use systems::{
-    combat::{collision_system, explosion_damage_system, zombie_eat_system},
-    gameplay::{move_bullets, move_zombies, plant_action},
-    input::{button_system, cursor_system, input_system},
+    animation::{
+        depth_sort_system, plant_visual_system, zombie_animation_system, zombie_death_system,
+    },
+    combat::{
+        collision_system, explosion_damage_system, fume_system, lobbed_hit_system, magnet_system,
+        spikeweed_system, squash_system, torchwood_system, zombie_damage_system, zombie_eat_system,
+    },
+    gameplay::{
+        blover_system, bungee_system, coffee_bean_system, dancer_system, digger_system, fog_system,
+        gargantuar_system, grave_buster_system, ice_trail_system, move_bullets, move_lobbed,
+        move_zombies, plant_action, recharge_system, sleep_indicator_system, status_effect_system,
+        vault_system,
+    },
+    input::{button_system, cursor_system, input_system, settings_system},
     particles::particle_system,
     setup::setup,
-    spawning::spawn_zombies,
-    ui::ui_system,
+    spawning::{spawn_zombie_events, spawn_zombies},
+    ui::{damage_number_system, health_bar_system, ui_system},
 };
 
 fn main() {
+    // First argument picks an entry of `LEVELS`, the web build always starts on the first
+    let level = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse::<usize>().ok())
+        .and_then(|index| LEVELS.get(index))
+        .unwrap_or(&LEVELS[0]);
+
     App::new()
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
@@ -29,23 +49,71 @@ fn main() {
             ..default()
         }))
         .insert_resource(GameState::default())
+        .insert_resource(Lawn::new(level))
+        .insert_resource(Level::new(level))
+        .insert_resource(Director::default())
+        .insert_resource(Settings::default())
         .insert_resource(SpawnTimer(Timer::from_seconds(10.0, TimerMode::Repeating)))
+        .add_event::<DamageEvent>()
+        .add_event::<SpawnZombieEvent>()
         .add_systems(Startup, setup)
         .add_systems(
             Update,
             (
-                input_system,
-                button_system, // UI Clicks
-                cursor_system,
-                spawn_zombies,
-                plant_action,
-                move_bullets,
-                move_zombies,
-                zombie_eat_system, // Interactions
-                collision_system,
-                explosion_damage_system,
-                particle_system,
-                ui_system,
+                (
+                    input_system,
+                    button_system, // UI Clicks
+                    cursor_system,
+                    settings_system,
+                    ui_system,
+                ),
+                (
+                    spawn_zombies,
+                    spawn_zombie_events,
+                    plant_action,
+                    recharge_system,
+                    coffee_bean_system,
+                    blover_system,
+                    grave_buster_system,
+                    fog_system,
+                    sleep_indicator_system,
+                ),
+                (
+                    move_bullets,
+                    move_lobbed,
+                    move_zombies,
+                    vault_system,
+                    digger_system,
+                    gargantuar_system,
+                    bungee_system,
+                    ice_trail_system,
+                    dancer_system,
+                    status_effect_system,
+                ),
+                (
+                    (
+                        zombie_eat_system, // Interactions
+                        collision_system,
+                        lobbed_hit_system,
+                        spikeweed_system,
+                        squash_system,
+                        fume_system,
+                        magnet_system,
+                        torchwood_system,
+                        explosion_damage_system,
+                        particle_system,
+                    ),
+                    damage_number_system,
+                    zombie_damage_system, // After every hit of the frame is in
+                )
+                    .chain(),
+                (
+                    depth_sort_system,
+                    zombie_animation_system,
+                    zombie_death_system,
+                    plant_visual_system,
+                    health_bar_system,
+                ),
             ),
         )
         .run();
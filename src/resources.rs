@@ -1,10 +1,13 @@
 use crate::components::{PlantType, Tool};
+use crate::constants::{COLS, GRAVE_COUNT, ROWS};
 use bevy::prelude::*;
+use bevy::utils::HashMap;
 
 #[derive(Resource)]
 pub struct GameState {
     pub sun: u32,
     pub selected_tool: Tool,
+    pub recharging: HashMap<PlantType, Timer>, // Seed packets not usable yet
 }
 
 impl Default for GameState {
@@ -12,9 +15,130 @@ impl Default for GameState {
         Self {
             sun: 150,
             selected_tool: Tool::Plant(PlantType::Peashooter),
+            recharging: HashMap::new(),
         }
     }
 }
 
 #[derive(Resource)]
 pub struct SpawnTimer(pub Timer);
+
+// Wave counter the spawner turns into a point budget for mixing zombie kinds
+#[derive(Resource, Default)]
+pub struct Director {
+    pub wave: u32,
+}
+
+// Player toggles, see `settings_system`
+#[derive(Resource, Default)]
+pub struct Settings {
+    pub show_health: bool, // Health bars over damaged plants and zombies, floating damage numbers
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeOfDay {
+    Day,
+    Night,
+}
+
+#[derive(Resource)]
+pub struct Level {
+    pub time_of_day: TimeOfDay,
+    pub graves: usize,
+    pub fog: bool,
+    pub fog_cleared: f32, // Seconds left before the fog comes back
+}
+
+impl Level {
+    pub const fn new(data: &LevelData) -> Self {
+        Self {
+            time_of_day: data.time_of_day,
+            graves: data.graves,
+            fog: data.fog,
+            fog_cleared: 0.0,
+        }
+    }
+}
+
+// What a level starts with, picked once at startup
+pub struct LevelData {
+    pub time_of_day: TimeOfDay,    // Mushrooms sleep through the day
+    pub pool_rows: &'static [i32], // Water lanes, the rest of the lawn is grass
+    pub graves: usize,             // Spread over grass in the back half of the lawn
+    pub fog: bool,                 // Covers the right side of the lawn
+}
+
+pub const LEVELS: [LevelData; 4] = [
+    // Front yard
+    LevelData {
+        time_of_day: TimeOfDay::Day,
+        pool_rows: &[],
+        graves: 0,
+        fog: false,
+    },
+    // Pool lane through the middle row
+    LevelData {
+        time_of_day: TimeOfDay::Day,
+        pool_rows: &[ROWS / 2],
+        graves: 0,
+        fog: false,
+    },
+    // Front yard at night
+    LevelData {
+        time_of_day: TimeOfDay::Night,
+        pool_rows: &[],
+        graves: GRAVE_COUNT,
+        fog: false,
+    },
+    // Pool at night, in the fog
+    LevelData {
+        time_of_day: TimeOfDay::Night,
+        pool_rows: &[ROWS / 2],
+        graves: GRAVE_COUNT,
+        fog: true,
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Terrain {
+    Grass,
+    Water, // Needs a Lily Pad before anything else can be planted
+}
+
+#[derive(Resource)]
+pub struct Lawn {
+    pub tiles: [[Terrain; COLS as usize]; ROWS as usize],
+    pub ice: [[f32; COLS as usize]; ROWS as usize], // Seconds left on each cell's ice, no planting
+}
+
+impl Default for Lawn {
+    fn default() -> Self {
+        Self {
+            tiles: [[Terrain::Grass; COLS as usize]; ROWS as usize],
+            ice: [[0.0; COLS as usize]; ROWS as usize],
+        }
+    }
+}
+
+impl Lawn {
+    // Grass everywhere except the level's pool lanes
+    #[allow(clippy::cast_sign_loss)]
+    pub fn new(level: &LevelData) -> Self {
+        let mut lawn = Self::default();
+        for &row in level.pool_rows {
+            lawn.tiles[row as usize] = [Terrain::Water; COLS as usize];
+        }
+        lawn
+    }
+
+    // Callers pass in-grid cells
+    #[allow(clippy::cast_sign_loss)]
+    pub const fn terrain(&self, col: i32, row: i32) -> Terrain {
+        self.tiles[row as usize][col as usize]
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    pub fn iced(&self, col: i32, row: i32) -> bool {
+        self.ice[row as usize][col as usize] > 0.0
+    }
+}
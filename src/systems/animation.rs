@@ -0,0 +1,215 @@
+use crate::components::{
+    Bullet, Buttered, Chilled, Dying, Lobbed, PartKind, Particle, Plant, PlantAnimation, PlantPart,
+    PlantType, Zombie, ZombieAnimation, ZombiePart, ZombieState,
+};
+use crate::constants::{
+    CHILL_SPEED_FACTOR, EAT_BOB_HEIGHT, EAT_BOB_SPEED, GRID_START_Y, GROUND_DEPTH, HIT_FLASH_TIME,
+    LEG_SWING_ANGLE, MINE_BLINK_SPEED, PLANT_BITE_FLASH_INTERVAL, PLANT_DAMAGE_STAGES,
+    PROJECTILE_DEPTH, RECOIL_SQUASH, RECOIL_TIME, ROWS, ROW_DEPTH, SCREEN_WIDTH, TILE_SIZE,
+    WALK_CYCLE_SPEED, ZOMBIE_DEPTH, ZOMBIE_DEPTH_SPREAD,
+};
+use bevy::prelude::*;
+
+// Poses every zombie from its state: legs swing while walking, head and arm bob while eating.
+// The arm falls off at half health, and a hit flashes the whole body white for a moment
+#[allow(clippy::type_complexity)]
+pub fn zombie_animation_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut zombie_query: Query<(
+        &Zombie,
+        &mut ZombieAnimation,
+        &Children,
+        Has<Buttered>,
+        Has<Chilled>,
+    )>,
+    mut part_query: Query<(&ZombiePart, &mut Transform, &mut Sprite)>,
+) {
+    for (zombie, mut animation, children, buttered, chilled) in &mut zombie_query {
+        // Butter freezes the pose, the chill slows it like the walk
+        let rate = if buttered {
+            0.0
+        } else if chilled {
+            CHILL_SPEED_FACTOR
+        } else {
+            1.0
+        };
+        animation.clock += time.delta_seconds() * rate;
+        animation.flash = (animation.flash - time.delta_seconds()).max(0.0);
+
+        let drop_arm = !animation.arm_dropped && zombie.health <= zombie.kind.stats().health / 2.0;
+        if drop_arm {
+            animation.arm_dropped = true;
+        }
+
+        let walking = matches!(
+            zombie.state,
+            ZombieState::Walking | ZombieState::ChangingLane(_)
+        );
+        let eating = matches!(zombie.state, ZombieState::Eating(_));
+        let swing = (animation.clock * WALK_CYCLE_SPEED).sin();
+        let bob = (animation.clock * EAT_BOB_SPEED).sin();
+
+        for &child in children {
+            let Ok((part, mut transform, mut sprite)) = part_query.get_mut(child) else {
+                continue;
+            };
+
+            if drop_arm && part.kind == PartKind::Arm {
+                sprite.color = part.color;
+                detach_part(&mut commands, child, Vec2::new(20.0, -80.0));
+                continue;
+            }
+
+            sprite.color = if animation.flash > 0.0 {
+                Color::WHITE
+            } else {
+                part.color
+            };
+
+            let mut translation = part.base;
+            let mut angle = 0.0;
+            match part.kind {
+                PartKind::Leg(side) if walking => angle = side * swing * LEG_SWING_ANGLE,
+                PartKind::Head if eating => translation.y -= bob.abs() * EAT_BOB_HEIGHT,
+                PartKind::Arm if eating => angle = bob * LEG_SWING_ANGLE,
+                _ => {}
+            }
+            transform.translation = translation;
+            transform.rotation = Quat::from_rotation_z(angle);
+        }
+    }
+}
+
+// Killed zombies lose their head and fall over backwards, then disappear
+pub fn zombie_death_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Transform, &mut Dying, &Children)>,
+    mut part_query: Query<(&ZombiePart, &mut Sprite)>,
+) {
+    for (entity, mut transform, mut dying, children) in &mut query {
+        if dying.is_added() {
+            for &child in children {
+                let Ok((part, mut sprite)) = part_query.get_mut(child) else {
+                    continue;
+                };
+                sprite.color = part.color;
+                if part.kind == PartKind::Head {
+                    detach_part(
+                        &mut commands,
+                        child,
+                        Vec2::new(-dying.direction * 30.0, -60.0),
+                    );
+                }
+            }
+        }
+
+        dying.timer.tick(time.delta());
+        let fall = dying.timer.fraction() * std::f32::consts::FRAC_PI_2;
+        transform.rotation = Quat::from_rotation_z(dying.direction * fall);
+        if dying.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+// Shows what a plant is going through: a flash for every bite, Wall-nut cracks as it wears
+// down, the Potato Mine popping up and blinking once armed, pea shooters squashing on each shot
+#[allow(clippy::type_complexity)]
+pub fn plant_visual_system(
+    time: Res<Time>,
+    mut plant_query: Query<(&Plant, &mut PlantAnimation, &mut Transform, &Children)>,
+    mut part_query: Query<(&PlantPart, &mut Transform, &mut Visibility), Without<Plant>>,
+) {
+    for (plant, mut animation, mut transform, children) in &mut plant_query {
+        let dt = time.delta_seconds();
+        animation.clock += dt;
+
+        // Eating is continuous, so the bites show as a flash every so often
+        animation.bite = (animation.bite - dt).max(0.0);
+        if plant.health < animation.last_health && animation.bite <= 0.0 {
+            animation.bite = PLANT_BITE_FLASH_INTERVAL;
+        }
+        animation.last_health = plant.health;
+        let flashing = animation.bite > PLANT_BITE_FLASH_INTERVAL - HIT_FLASH_TIME;
+
+        if plant.kind.is_pea_shooter() {
+            animation.recoil = (animation.recoil - dt).max(0.0);
+            if plant.timer.finished() {
+                animation.recoil = RECOIL_TIME;
+            }
+            let squash = RECOIL_SQUASH * animation.recoil / RECOIL_TIME;
+            transform.scale = Vec3::new(1.0 + squash, 1.0 - squash, 1.0);
+        }
+
+        #[allow(clippy::cast_sign_loss)]
+        let stage = ((1.0 - plant.health / animation.max_health) * PLANT_DAMAGE_STAGES) as u8;
+        let armed = plant.kind == PlantType::PotatoMine && plant.armed;
+        let blink = (animation.clock * MINE_BLINK_SPEED).sin() > 0.0;
+
+        for &child in children {
+            let Ok((part, mut part_transform, mut visibility)) = part_query.get_mut(child) else {
+                continue;
+            };
+            let visible = match *part {
+                PlantPart::MineBody => {
+                    part_transform.translation.y = if armed { -10.0 } else { -20.0 };
+                    true
+                }
+                PlantPart::MineLight => {
+                    part_transform.translation.y = if armed { 0.0 } else { -10.0 };
+                    armed && blink
+                }
+                PlantPart::Crack(from) => stage >= from,
+                PlantPart::Flash => flashing,
+            };
+            *visibility = if visible {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
+
+// Keeps everything that moves in its row's depth band: zombies by their lane, with the
+// ones further left on top, and projectiles by whichever row they're flying over
+#[allow(clippy::type_complexity)]
+pub fn depth_sort_system(
+    mut zombie_query: Query<(&mut Transform, &Zombie)>,
+    mut bullet_query: Query<&mut Transform, (With<Bullet>, Without<Zombie>)>,
+    mut lobbed_query: Query<(&mut Transform, &Lobbed), (Without<Bullet>, Without<Zombie>)>,
+) {
+    for (mut transform, zombie) in &mut zombie_query {
+        let ahead = ((SCREEN_WIDTH / 2.0 - transform.translation.x) / SCREEN_WIDTH).clamp(0.0, 1.0);
+        transform.translation.z =
+            row_depth(zombie.row, ahead.mul_add(ZOMBIE_DEPTH_SPREAD, ZOMBIE_DEPTH));
+    }
+
+    for mut transform in &mut bullet_query {
+        let row = ((transform.translation.y - GRID_START_Y) / TILE_SIZE).round() as i32;
+        transform.translation.z = row_depth(row, PROJECTILE_DEPTH);
+    }
+
+    for (mut transform, lobbed) in &mut lobbed_query {
+        transform.translation.z = row_depth(lobbed.row, PROJECTILE_DEPTH);
+    }
+}
+
+// z for something standing in `row`, `offset` places it inside the row's band
+pub fn row_depth(row: i32, offset: f32) -> f32 {
+    ((ROWS - 1 - row.clamp(0, ROWS - 1)) as f32).mul_add(ROW_DEPTH, GROUND_DEPTH + offset)
+}
+
+// Part comes off the zombie and drops away as a particle
+fn detach_part(commands: &mut Commands, part: Entity, velocity: Vec2) {
+    commands
+        .entity(part)
+        .remove_parent_in_place()
+        .remove::<ZombiePart>()
+        .insert(Particle {
+            velocity,
+            timer: Timer::from_seconds(0.8, TimerMode::Once),
+        });
+}
@@ -1,73 +1,182 @@
-use crate::components::{Bullet, Explosion, Particle, Plant, PlantType, Zombie, ZombieState};
-use crate::constants::ZOMBIE_EAT_DPS;
+use crate::components::{
+    Backup, Bullet, BulletKind, Buttered, Chilled, Dying, Equipment, EquipmentKind,
+    EquipmentMaterial, Explosion, GridCell, Laddered, Lobbed, LobbedKind, Particle, Plant,
+    PlantType, Pole, SquashLeap, TargetLayer, Vault, Zombie, ZombieAbility, ZombieAnimation,
+    ZombieState,
+};
+use crate::constants::{
+    BUTTER_DURATION, CHILL_DURATION, CHILL_SPEED_FACTOR, COLOR_BUTTER, COLOR_CHILLED, COLOR_FUME,
+    COLS, DEATH_FALL_TIME, FIRE_SPLASH_RADIUS, FUMESHROOM_RANGE, FUME_DAMAGE, GARLIC_BITE_DAMAGE,
+    GRID_START_X, HIT_FLASH_TIME, MAGNET_RADIUS, MELON_SPLASH_RADIUS, NEWSPAPER_RAGE_FACTOR,
+    POGO_HOP_HEIGHT, ROWS, SPIKEWEED_DAMAGE, SQUASH_DAMAGE, SQUASH_LEAP_HEIGHT, SQUASH_LEAP_TIME,
+    TILE_SIZE, VAULT_DISTANCE, VAULT_HEIGHT, VAULT_TIME,
+};
+use crate::events::{DamageEvent, Delivery};
 use bevy::prelude::*;
 use rand::Rng;
 
 // Logic for zombies eating plants + collisions
+#[allow(clippy::type_complexity)]
 pub fn zombie_eat_system(
     mut commands: Commands,
     time: Res<Time>,
-    mut zombie_query: Query<(Entity, &Transform, &mut Zombie)>,
+    mut zombie_query: Query<(
+        Entity,
+        &Transform,
+        &mut Zombie,
+        Option<&Pole>,
+        Option<&Children>,
+        Has<Buttered>,
+        Has<Chilled>,
+    )>,
     mut plant_query: Query<(Entity, &Transform, &mut Plant)>,
+    cell_query: Query<(Entity, &GridCell), With<Plant>>,
+    equipment_query: Query<&Equipment>,
+    ladder_query: Query<(), With<Laddered>>,
 ) {
-    for (_zombie_entity, zombie_transform, mut zombie) in &mut zombie_query {
+    for (zombie_entity, zombie_transform, mut zombie, pole, children, buttered, chilled) in
+        &mut zombie_query
+    {
+        // Butter holds the zombie in place, mouth included
+        if buttered {
+            continue;
+        }
+        // Tunnels and balloons pass under and over the plants
+        if zombie.layer != TargetLayer::Ground {
+            continue;
+        }
+
         match zombie.state {
             ZombieState::Walking => {
-                // Check if colliding with any plant
-                for (plant_entity, plant_transform, plant) in plant_query.iter() {
-                    let distance = zombie_transform
-                        .translation
-                        .truncate()
-                        .distance(plant_transform.translation.truncate());
-                    if distance < 40.0 {
-                        // Collision!
-
-                        // Special Case: Potato Mine
-                        if plant.kind == PlantType::PotatoMine && plant.armed {
-                            // BOOM - Spawn explosion
+                // Check if colliding with any plant, the outermost layer gets bitten first
+                let zombie_pos = zombie_transform.translation.truncate();
+                if let Some((plant_entity, plant_transform, plant)) = plant_query
+                    .iter()
+                    .filter(|(_, plant_transform, plant)| {
+                        // Ground traps are walked over, not eaten
+                        plant.kind.is_edible()
+                            && zombie_pos.distance(plant_transform.translation.truncate()) < 40.0
+                    })
+                    .max_by_key(|(_, _, plant)| plant.kind.layer())
+                {
+                    // Collision!
+
+                    // Special Case: Potato Mine
+                    if plant.kind == PlantType::PotatoMine && plant.armed {
+                        // BOOM - Spawn explosion
+                        commands.spawn((
+                            Explosion {
+                                timer: Timer::from_seconds(0.5, TimerMode::Once),
+                                radius: 120.0, // 3x3 approx (1.5 * 80)
+                                damage: 1000.0,
+                            },
+                            SpatialBundle {
+                                transform: *plant_transform,
+                                ..default()
+                            },
+                        ));
+                        commands.entity(plant_entity).despawn_recursive();
+
+                        // Spawn Particles
+                        let mut rng = rand::thread_rng();
+                        for _ in 0..12 {
+                            let vx: f32 = rng.gen_range(-150.0..150.0);
+                            let vy: f32 = rng.gen_range(-150.0..150.0);
                             commands.spawn((
-                                Explosion {
-                                    timer: Timer::from_seconds(0.5, TimerMode::Once),
-                                    radius: 120.0, // 3x3 approx (1.5 * 80)
-                                    damage: 1000.0,
-                                },
-                                SpatialBundle {
+                                SpriteBundle {
+                                    sprite: Sprite {
+                                        color: Color::rgb(0.8, 0.5, 0.2), // Potato color ish
+                                        custom_size: Some(Vec2::new(10.0, 10.0)),
+                                        ..default()
+                                    },
                                     transform: *plant_transform,
                                     ..default()
                                 },
+                                Particle {
+                                    velocity: Vec2::new(vx, vy),
+                                    timer: Timer::from_seconds(0.8, TimerMode::Once),
+                                },
                             ));
-                            commands.entity(plant_entity).despawn_recursive();
-
-                            // Spawn Particles
-                            let mut rng = rand::thread_rng();
-                            for _ in 0..12 {
-                                let vx: f32 = rng.gen_range(-150.0..150.0);
-                                let vy: f32 = rng.gen_range(-150.0..150.0);
-                                commands.spawn((
-                                    SpriteBundle {
-                                        sprite: Sprite {
-                                            color: Color::rgb(0.8, 0.5, 0.2), // Potato color ish
-                                            custom_size: Some(Vec2::new(10.0, 10.0)),
-                                            ..default()
-                                        },
-                                        transform: *plant_transform,
-                                        ..default()
-                                    },
-                                    Particle {
-                                        velocity: Vec2::new(vx, vy),
-                                        timer: Timer::from_seconds(0.8, TimerMode::Once),
-                                    },
-                                ));
-                            }
+                        }
+
+                        // Zombie is not despawned here, will be caught by explosion system
+                        continue;
+                    }
 
-                            // Zombie is not despawned here, will be caught by explosion system
-                            break;
+                    let tall_nut = plant_query.iter().any(|(_, transform, plant)| {
+                        plant.kind == PlantType::TallNut
+                            && zombie_pos.distance(transform.translation.truncate()) < 40.0
+                    });
+                    let plant_x = plant_transform.translation.x;
+                    let carried = |kind| {
+                        children.into_iter().flatten().copied().find(|item| {
+                            equipment_query
+                                .get(*item)
+                                .is_ok_and(|equipment| equipment.kind == kind)
+                        })
+                    };
+
+                    // The pole is used up on the first plant, vaulting it unless a Tall-nut is there
+                    if let Some(pole) = pole {
+                        commands.entity(pole.sprite).despawn_recursive();
+                        commands.entity(zombie_entity).remove::<Pole>();
+                        zombie.speed = zombie.kind.stats().walk_speed;
+                        if !tall_nut {
+                            vault_over(
+                                &mut commands,
+                                zombie_entity,
+                                zombie_pos,
+                                plant_x,
+                                VAULT_HEIGHT,
+                            );
+                            zombie.state = ZombieState::Vaulting;
+                            continue;
                         }
+                    }
+
+                    // The pogo stick hops every plant, until a Tall-nut knocks it away
+                    if let Some(stick) = carried(EquipmentKind::PogoStick) {
+                        if !tall_nut {
+                            vault_over(
+                                &mut commands,
+                                zombie_entity,
+                                zombie_pos,
+                                plant_x,
+                                POGO_HOP_HEIGHT,
+                            );
+                            zombie.state = ZombieState::Vaulting;
+                            continue;
+                        }
+                        drop_equipment(&mut commands, stick);
+                        zombie.speed = zombie.kind.stats().walk_speed;
+                    }
 
-                        // Normal: Start eating
-                        zombie.state = ZombieState::Eating(plant_entity);
-                        break; // Only eat one at a time
+                    // The ladder goes up against the first plant, then everyone climbs it.
+                    // Smashers flatten the plant instead
+                    let ladder = carried(EquipmentKind::Ladder);
+                    if let Some(ladder) = ladder {
+                        commands
+                            .entity(ladder)
+                            .remove::<Equipment>()
+                            .set_parent(plant_entity)
+                            .insert(Transform::from_xyz(-20.0, 10.0, 0.5));
+                        commands.entity(plant_entity).insert(Laddered);
                     }
+                    let laddered = ladder.is_some() || ladder_query.contains(plant_entity);
+                    if laddered && !zombie.kind.stats().smashes {
+                        vault_over(
+                            &mut commands,
+                            zombie_entity,
+                            zombie_pos,
+                            plant_x,
+                            VAULT_HEIGHT,
+                        );
+                        zombie.state = ZombieState::Vaulting;
+                        continue;
+                    }
+
+                    // Normal: Start eating
+                    zombie.state = ZombieState::Eating(plant_entity); // Only eat one at a time
                 }
             }
             ZombieState::Eating(plant_entity) => {
@@ -114,7 +223,37 @@ pub fn zombie_eat_system(
                         continue;
                     }
 
-                    plant.health -= ZOMBIE_EAT_DPS * time.delta_seconds();
+                    // A smash flattens the whole cell, whatever is stacked there, and frees it
+                    if zombie.kind.stats().smashes {
+                        if let Ok((_, target_cell)) = cell_query.get(p_entity) {
+                            for (entity, cell) in cell_query.iter() {
+                                if cell.x == target_cell.x && cell.y == target_cell.y {
+                                    commands.entity(entity).despawn_recursive();
+                                }
+                            }
+                        }
+                        zombie.state = ZombieState::Walking;
+                        continue;
+                    }
+
+                    // One bite of Garlic is enough, the zombie turns off into a neighbouring lane
+                    if plant.kind == PlantType::Garlic {
+                        plant.health -= GARLIC_BITE_DAMAGE;
+                        if plant.health <= 0.0 {
+                            commands.entity(p_entity).despawn_recursive();
+                        }
+                        zombie.state = garlic_detour(zombie.row)
+                            .map_or(ZombieState::Walking, ZombieState::ChangingLane);
+                        continue;
+                    }
+
+                    let bite_dps = zombie.kind.stats().bite_dps;
+                    let dps = if chilled {
+                        bite_dps * CHILL_SPEED_FACTOR
+                    } else {
+                        bite_dps
+                    };
+                    plant.health -= dps * time.delta_seconds();
                     if plant.health <= 0.0 {
                         commands.entity(p_entity).despawn_recursive();
                         zombie.state = ZombieState::Walking;
@@ -124,50 +263,504 @@ pub fn zombie_eat_system(
                     zombie.state = ZombieState::Walking;
                 }
             }
+            // Too busy walking away or flying over to bite anything
+            ZombieState::ChangingLane(_)
+            | ZombieState::Vaulting
+            | ZombieState::Dancing
+            | ZombieState::Hanging => {}
         }
     }
 }
 
+// Random neighbouring row that is still on the lawn
+fn garlic_detour(row: i32) -> Option<i32> {
+    let rows: Vec<i32> = [row - 1, row + 1]
+        .into_iter()
+        .filter(|r| (0..ROWS).contains(r))
+        .collect();
+    if rows.is_empty() {
+        return None;
+    }
+    Some(rows[rand::thread_rng().gen_range(0..rows.len())])
+}
+
+#[allow(clippy::type_complexity)]
 pub fn collision_system(
     mut commands: Commands,
-    bullet_query: Query<(Entity, &Transform), With<Bullet>>,
-    mut zombie_query: Query<(Entity, &Transform, &mut Zombie)>,
+    mut damage_events: EventWriter<DamageEvent>,
+    bullet_query: Query<(Entity, &Transform, &Bullet)>,
+    mut zombie_query: Query<(Entity, &Transform, &Zombie, Option<&mut Chilled>)>,
+) {
+    for (bullet_entity, bullet_transform, bullet) in bullet_query.iter() {
+        let bullet_pos = bullet_transform.translation.truncate();
+
+        // hit radius, one zombie per pea
+        let Some((zombie_entity, zombie_transform, _, chilled)) =
+            zombie_query.iter_mut().find(|(_, transform, zombie, _)| {
+                bullet.kind.hits(zombie.layer)
+                    && transform.translation.truncate().distance(bullet_pos) < 40.0
+            })
+        else {
+            continue;
+        };
+        commands.entity(bullet_entity).despawn();
+        damage_events.send(DamageEvent {
+            zombie: zombie_entity,
+            amount: bullet.damage,
+            delivery: Delivery::Straight,
+            direction: bullet.velocity.normalize_or_zero(),
+        });
+
+        match bullet.kind {
+            BulletKind::Pea | BulletKind::Spore | BulletKind::Star | BulletKind::Spike => {}
+            BulletKind::FrozenPea => chill(&mut commands, zombie_entity, chilled),
+            BulletKind::FirePea => {
+                // Fire melts the chill away
+                if let Some(chilled) = chilled {
+                    commands.entity(chilled.tint).despawn_recursive();
+                    commands.entity(zombie_entity).remove::<Chilled>();
+                }
+
+                // Splash a third of the damage onto zombies around the target
+                let center = zombie_transform.translation.truncate();
+                for (other_entity, other_transform, other, _) in zombie_query.iter() {
+                    if other_entity == zombie_entity
+                        || !bullet.kind.hits(other.layer)
+                        || other_transform.translation.truncate().distance(center)
+                            > FIRE_SPLASH_RADIUS
+                    {
+                        continue;
+                    }
+                    damage_events.send(DamageEvent {
+                        zombie: other_entity,
+                        amount: bullet.damage / 3.0,
+                        delivery: Delivery::Area,
+                        direction: Vec2::ZERO,
+                    });
+                }
+            }
+        }
+    }
+}
+
+// Slows a zombie down, or refreshes the slow if it's already chilled
+fn chill(commands: &mut Commands, zombie_entity: Entity, chilled: Option<Mut<Chilled>>) {
+    if let Some(mut chilled) = chilled {
+        chilled.timer.reset();
+        return;
+    }
+
+    let tint = commands
+        .spawn(SpriteBundle {
+            sprite: Sprite {
+                color: COLOR_CHILLED,
+                custom_size: Some(Vec2::new(40.0, 110.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 5.0, 0.5),
+            ..default()
+        })
+        .id();
+    commands
+        .entity(zombie_entity)
+        .add_child(tint)
+        .insert(Chilled {
+            timer: Timer::from_seconds(CHILL_DURATION, TimerMode::Once),
+            tint,
+        });
+}
+
+// Peas crossing a Torchwood catch fire, frozen peas thaw back into normal ones
+pub fn torchwood_system(
+    plant_query: Query<(Entity, &Plant, &Transform)>,
+    mut bullet_query: Query<(&Transform, &mut Bullet, &mut Sprite)>,
+) {
+    for (torch_entity, plant, torch_transform) in plant_query.iter() {
+        if plant.kind != PlantType::Torchwood {
+            continue;
+        }
+
+        for (bullet_transform, mut bullet, mut sprite) in &mut bullet_query {
+            let offset =
+                bullet_transform.translation.truncate() - torch_transform.translation.truncate();
+            if offset.x.abs() > TILE_SIZE / 2.0
+                || offset.y.abs() > TILE_SIZE / 2.0
+                || bullet.torchwood == Some(torch_entity)
+            {
+                continue;
+            }
+
+            bullet.torchwood = Some(torch_entity);
+            match bullet.kind {
+                BulletKind::Pea => {
+                    bullet.kind = BulletKind::FirePea;
+                    bullet.damage *= 2.0;
+                }
+                BulletKind::FrozenPea => bullet.kind = BulletKind::Pea,
+                BulletKind::FirePea | BulletKind::Spore | BulletKind::Star | BulletKind::Spike => {}
+            }
+            sprite.color = bullet.kind.color();
+        }
+    }
+}
+
+// Spikeweed hurts every zombie standing on its cell, once per plant tick. A Zomboni driving
+// over it pops its tyres and is wrecked, taking the Spikeweed with it
+pub fn spikeweed_system(
+    mut commands: Commands,
+    mut damage_events: EventWriter<DamageEvent>,
+    plant_query: Query<(Entity, &Plant, &Transform, &GridCell)>,
+    zombie_query: Query<(Entity, &Transform, &Zombie)>,
+) {
+    for (plant_entity, plant, plant_transform, cell) in plant_query.iter() {
+        if plant.kind != PlantType::Spikeweed || !plant.timer.finished() {
+            continue;
+        }
+
+        for (zombie_entity, zombie_transform, zombie) in zombie_query.iter() {
+            let dx = (zombie_transform.translation.x - plant_transform.translation.x).abs();
+            if zombie.row != cell.y || zombie.layer != TargetLayer::Ground || dx >= TILE_SIZE / 2.0
+            {
+                continue;
+            }
+            let wrecks = zombie.kind.stats().ability == ZombieAbility::IceTrail;
+            damage_events.send(DamageEvent {
+                zombie: zombie_entity,
+                amount: if wrecks {
+                    zombie.health
+                } else {
+                    SPIKEWEED_DAMAGE
+                },
+                delivery: Delivery::Area,
+                direction: Vec2::ZERO,
+            });
+            // The Spikeweed is spent on the first Zomboni, a second one in the cell drives on
+            if wrecks {
+                commands.entity(plant_entity).despawn_recursive();
+                break;
+            }
+        }
+    }
+}
+
+// Magnet-shroom pulls the nearest metal item off a zombie in range, then holds it for a while
+pub fn magnet_system(
+    mut commands: Commands,
+    mut plant_query: Query<(Entity, &mut Plant, &Transform)>,
+    equipment_query: Query<(Entity, &Equipment, &Parent)>,
+    mut zombie_query: Query<(&Transform, &mut Zombie)>,
+) {
+    let mut pulled: Vec<Entity> = Vec::new();
+
+    for (magnet_entity, mut plant, magnet_transform) in &mut plant_query {
+        if plant.kind != PlantType::MagnetShroom || plant.asleep {
+            continue;
+        }
+
+        // Can't pull again until the held item is gone
+        let held = equipment_query
+            .iter()
+            .find(|(_, _, parent)| parent.get() == magnet_entity);
+        if let Some((item, ..)) = held {
+            if plant.timer.finished() {
+                commands.entity(item).despawn_recursive();
+            }
+            continue;
+        }
+
+        let magnet_pos = magnet_transform.translation.truncate();
+        let Some((item, kind, owner, _)) = equipment_query
+            .iter()
+            .filter(|(item, equipment, _)| {
+                equipment.kind.material() == EquipmentMaterial::Metal && !pulled.contains(item)
+            })
+            .filter_map(|(item, equipment, parent)| {
+                let (zombie_transform, _) = zombie_query.get(parent.get()).ok()?;
+                let dist = zombie_transform.translation.truncate().distance(magnet_pos);
+                (dist <= MAGNET_RADIUS).then_some((item, equipment.kind, parent.get(), dist))
+            })
+            .min_by(|(.., a), (.., b)| a.total_cmp(b))
+        else {
+            continue;
+        };
+
+        // Without its stick a Pogo zombie is back on its feet
+        if kind == EquipmentKind::PogoStick {
+            if let Ok((_, mut zombie)) = zombie_query.get_mut(owner) {
+                zombie.speed = zombie.kind.stats().walk_speed;
+            }
+        }
+
+        pulled.push(item);
+        commands
+            .entity(item)
+            .set_parent(magnet_entity)
+            .insert(Transform::from_xyz(0.0, 40.0, 0.5));
+        plant.timer.reset();
+    }
+}
+
+// Fume-shroom hits every zombie up to four tiles ahead in its lane, through shields
+pub fn fume_system(
+    mut commands: Commands,
+    mut damage_events: EventWriter<DamageEvent>,
+    plant_query: Query<(&Plant, &Transform, &GridCell)>,
+    zombie_query: Query<(Entity, &Transform, &Zombie)>,
+) {
+    for (plant, plant_transform, cell) in plant_query.iter() {
+        if plant.kind != PlantType::FumeShroom || plant.asleep || !plant.timer.finished() {
+            continue;
+        }
+
+        let in_range = |transform: &Transform, zombie: &Zombie| {
+            let dx = transform.translation.x - plant_transform.translation.x;
+            zombie.row == cell.y
+                && zombie.layer == TargetLayer::Ground
+                && (0.0..=FUMESHROOM_RANGE).contains(&dx)
+        };
+        if !zombie_query
+            .iter()
+            .any(|(_, transform, zombie)| in_range(transform, zombie))
+        {
+            continue;
+        }
+
+        for (zombie_entity, transform, zombie) in zombie_query.iter() {
+            if in_range(transform, zombie) {
+                damage_events.send(DamageEvent {
+                    zombie: zombie_entity,
+                    amount: FUME_DAMAGE,
+                    delivery: Delivery::Area,
+                    direction: Vec2::ZERO,
+                });
+            }
+        }
+
+        // Short-lived cloud over the covered tiles
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_FUME,
+                    custom_size: Some(Vec2::new(FUMESHROOM_RANGE, 30.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(
+                    plant_transform.translation + Vec3::new(FUMESHROOM_RANGE / 2.0, -10.0, 3.0),
+                ),
+                ..default()
+            },
+            Particle {
+                velocity: Vec2::ZERO,
+                timer: Timer::from_seconds(0.3, TimerMode::Once),
+            },
+        ));
+    }
+}
+
+// Squash waits for a zombie on its own or a neighbouring cell, then leaps onto it
+#[allow(clippy::type_complexity)]
+pub fn squash_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut squash_query: Query<
+        (
+            Entity,
+            &Plant,
+            Option<&GridCell>,
+            &mut Transform,
+            Option<&mut SquashLeap>,
+        ),
+        Without<Zombie>,
+    >,
+    zombie_query: Query<(Entity, &Transform, &Zombie), Without<Plant>>,
+) {
+    for (squash_entity, plant, cell, mut transform, leap) in &mut squash_query {
+        if plant.kind != PlantType::Squash {
+            continue;
+        }
+
+        let Some(mut leap) = leap else {
+            let Some(cell) = cell else {
+                continue;
+            };
+            // Own cell first, then ahead, then behind
+            let row = cell.y;
+            let target_col = [cell.x, cell.x + 1, cell.x - 1]
+                .into_iter()
+                .filter(|col| (0..COLS).contains(col))
+                .find(|&col| {
+                    let col_x = (col as f32).mul_add(TILE_SIZE, GRID_START_X);
+                    zombie_query.iter().any(|(_, z_transform, zombie)| {
+                        zombie.row == row
+                            && zombie.layer == TargetLayer::Ground
+                            && (z_transform.translation.x - col_x).abs() < TILE_SIZE / 2.0
+                    })
+                });
+
+            if let Some(col) = target_col {
+                // Leaves its cell on take-off, nothing can bite it in the air
+                commands
+                    .entity(squash_entity)
+                    .remove::<GridCell>()
+                    .insert(SquashLeap {
+                        row,
+                        start: transform.translation.truncate(),
+                        target: Vec2::new(
+                            (col as f32).mul_add(TILE_SIZE, GRID_START_X),
+                            transform.translation.y,
+                        ),
+                        timer: Timer::from_seconds(SQUASH_LEAP_TIME, TimerMode::Once),
+                    });
+            }
+            continue;
+        };
+
+        // Hop up and over, then slam down
+        leap.timer.tick(time.delta());
+        let t = leap.timer.fraction();
+        let ground = leap.start.lerp(leap.target, t);
+        let height = SQUASH_LEAP_HEIGHT * (t * std::f32::consts::PI).sin();
+        transform.translation.x = ground.x;
+        transform.translation.y = ground.y + height;
+
+        if leap.timer.finished() {
+            for (zombie_entity, z_transform, zombie) in zombie_query.iter() {
+                if zombie.row == leap.row
+                    && zombie.layer == TargetLayer::Ground
+                    && (z_transform.translation.x - leap.target.x).abs() < TILE_SIZE / 2.0
+                {
+                    damage_events.send(DamageEvent {
+                        zombie: zombie_entity,
+                        amount: SQUASH_DAMAGE,
+                        delivery: Delivery::Area,
+                        direction: Vec2::ZERO,
+                    });
+                }
+            }
+            commands.entity(squash_entity).despawn_recursive();
+        }
+    }
+}
+
+// Lobbed shots only hit on landing, so nothing in front of the target blocks them
+#[allow(clippy::type_complexity)]
+pub fn lobbed_hit_system(
+    mut commands: Commands,
+    mut damage_events: EventWriter<DamageEvent>,
+    lobbed_query: Query<(Entity, &Lobbed)>,
+    mut zombie_query: Query<(
+        Entity,
+        &Transform,
+        &Zombie,
+        Option<&mut Buttered>,
+        Option<&mut Chilled>,
+    )>,
 ) {
-    for (bullet_entity, bullet_transform) in bullet_query.iter() {
-        for (zombie_entity, zombie_transform, mut zombie) in &mut zombie_query {
-            let distance = bullet_transform
-                .translation
-                .truncate()
-                .distance(zombie_transform.translation.truncate());
-            if distance < 40.0 {
-                // hit radius
-                commands.entity(bullet_entity).despawn();
-                zombie.health -= 10.0;
-                if zombie.health <= 0.0 {
-                    commands.entity(zombie_entity).despawn_recursive();
+    for (lobbed_entity, lobbed) in lobbed_query.iter() {
+        if !lobbed.timer.finished() {
+            continue;
+        }
+        commands.entity(lobbed_entity).despawn();
+
+        // Closest zombie in the lane to the landing point
+        let Some((zombie_entity, zombie_transform, _, buttered, chilled)) = zombie_query
+            .iter_mut()
+            .filter(|(_, transform, zombie, ..)| {
+                zombie.row == lobbed.row
+                    && zombie.layer == TargetLayer::Ground
+                    && (transform.translation.x - lobbed.target.x).abs() < 40.0
+            })
+            .min_by(|(_, a, ..), (_, b, ..)| {
+                (a.translation.x - lobbed.target.x)
+                    .abs()
+                    .total_cmp(&(b.translation.x - lobbed.target.x).abs())
+            })
+        else {
+            continue;
+        };
+
+        let center = zombie_transform.translation.truncate();
+        damage_events.send(DamageEvent {
+            zombie: zombie_entity,
+            amount: lobbed.kind.damage(),
+            delivery: Delivery::Lobbed,
+            direction: Vec2::NEG_Y,
+        });
+        match lobbed.kind {
+            LobbedKind::Butter => {
+                if let Some(mut buttered) = buttered {
+                    buttered.timer.reset();
+                } else {
+                    let splat = commands
+                        .spawn(SpriteBundle {
+                            sprite: Sprite {
+                                color: COLOR_BUTTER,
+                                custom_size: Some(Vec2::new(24.0, 12.0)),
+                                ..default()
+                            },
+                            transform: Transform::from_xyz(0.0, 55.0, 0.4),
+                            ..default()
+                        })
+                        .id();
+                    commands
+                        .entity(zombie_entity)
+                        .add_child(splat)
+                        .insert(Buttered {
+                            timer: Timer::from_seconds(BUTTER_DURATION, TimerMode::Once),
+                            splat,
+                        });
                 }
             }
+            LobbedKind::WinterMelon => chill(&mut commands, zombie_entity, chilled),
+            LobbedKind::Cabbage | LobbedKind::Kernel | LobbedKind::Melon => {}
+        }
+
+        // Melons splash a third of their damage around the target, Winter Melon chills it all
+        if !lobbed.kind.splashes() {
+            continue;
+        }
+        for (other_entity, other_transform, other, _, other_chilled) in &mut zombie_query {
+            if other_entity == zombie_entity
+                || other.layer != TargetLayer::Ground
+                || other_transform.translation.truncate().distance(center) > MELON_SPLASH_RADIUS
+            {
+                continue;
+            }
+            damage_events.send(DamageEvent {
+                zombie: other_entity,
+                amount: lobbed.kind.damage() / 3.0,
+                delivery: Delivery::Area,
+                direction: Vec2::ZERO,
+            });
+            if lobbed.kind == LobbedKind::WinterMelon {
+                chill(&mut commands, other_entity, other_chilled);
+            }
         }
     }
 }
+
 // Explosion logic
 pub fn explosion_damage_system(
     mut commands: Commands,
+    mut damage_events: EventWriter<DamageEvent>,
     time: Res<Time>,
     mut explosion_query: Query<(Entity, &Transform, &mut Explosion)>,
-    mut zombie_query: Query<(Entity, &Transform, &mut Zombie)>,
+    zombie_query: Query<(Entity, &Transform), With<Zombie>>,
 ) {
     for (exp_entity, exp_transform, mut explosion) in &mut explosion_query {
-        // Apply damage to all zombies in range
+        // Apply damage to all zombies in range, blasts reach every layer
         let exp_pos = exp_transform.translation.truncate();
 
-        for (zombie_entity, zombie_transform, mut zombie) in &mut zombie_query {
+        for (zombie_entity, zombie_transform) in zombie_query.iter() {
             let z_pos = zombie_transform.translation.truncate();
             if exp_pos.distance(z_pos) <= explosion.radius {
-                zombie.health -= explosion.damage * time.delta_seconds();
-                if zombie.health <= 0.0 {
-                    commands.entity(zombie_entity).despawn_recursive();
-                }
+                damage_events.send(DamageEvent {
+                    zombie: zombie_entity,
+                    amount: explosion.damage * time.delta_seconds(),
+                    delivery: Delivery::Area,
+                    direction: Vec2::ZERO,
+                });
             }
         }
 
@@ -177,3 +770,82 @@ pub fn explosion_damage_system(
         }
     }
 }
+
+// Applies this frame's damage: shields and armor soak it up first and fall off once spent
+pub fn zombie_damage_system(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+    mut zombie_query: Query<(&mut Zombie, &mut ZombieAnimation, Option<&Children>)>,
+    mut equipment_query: Query<&mut Equipment>,
+) {
+    for event in damage_events.read() {
+        let Ok((mut zombie, mut animation, children)) = zombie_query.get_mut(event.zombie) else {
+            continue;
+        };
+        // Already killed by an earlier hit this frame
+        if zombie.health <= 0.0 {
+            continue;
+        }
+        animation.flash = HIT_FLASH_TIME;
+
+        // Shield and armor soak up what they cover, rest goes to the body
+        let head_on = event.direction.x * zombie.direction < 0.0;
+        let mut damage = event.amount;
+        for item in children.into_iter().flatten() {
+            let Ok(mut equipment) = equipment_query.get_mut(*item) else {
+                continue;
+            };
+            if equipment.health <= 0.0 || !equipment.kind.blocks(event.delivery, head_on) {
+                continue;
+            }
+
+            let absorbed = damage.min(equipment.health);
+            equipment.health -= absorbed;
+            damage -= absorbed;
+            if equipment.health <= 0.0 {
+                drop_equipment(&mut commands, *item);
+                match equipment.kind {
+                    // Losing the paper makes the zombie furious
+                    EquipmentKind::Newspaper => zombie.speed *= NEWSPAPER_RAGE_FACTOR,
+                    // Popped, it drops down and walks like any other
+                    EquipmentKind::Balloon => zombie.layer = TargetLayer::Ground,
+                    _ => {}
+                }
+            }
+        }
+
+        zombie.health -= damage;
+        if zombie.health <= 0.0 {
+            // The body stays a moment to fall over, see `zombie_death_system`
+            commands
+                .entity(event.zombie)
+                .remove::<(Zombie, Vault, Backup)>()
+                .insert(Dying {
+                    timer: Timer::from_seconds(DEATH_FALL_TIME, TimerMode::Once),
+                    direction: zombie.direction,
+                });
+        }
+    }
+}
+
+// Jump or climb over the plant at `plant_x`, landing behind it, see `vault_system`
+fn vault_over(commands: &mut Commands, zombie: Entity, from: Vec2, plant_x: f32, height: f32) {
+    commands.entity(zombie).insert(Vault {
+        start: from,
+        target: Vec2::new(plant_x - VAULT_DISTANCE, from.y),
+        height,
+        timer: Timer::from_seconds(VAULT_TIME, TimerMode::Once),
+    });
+}
+
+// Spent shields and armor tumble off the zombie and fades out as a particle
+fn drop_equipment(commands: &mut Commands, item: Entity) {
+    commands
+        .entity(item)
+        .remove_parent_in_place()
+        .remove::<Equipment>()
+        .insert(Particle {
+            velocity: Vec2::new(40.0, -60.0),
+            timer: Timer::from_seconds(0.8, TimerMode::Once),
+        });
+}
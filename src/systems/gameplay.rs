@@ -1,47 +1,778 @@
-use crate::components::{Bullet, Plant, PlantType, Zombie, ZombieState};
-use crate::constants::{BULLET_SPEED, COLOR_BULLET, SCREEN_WIDTH, ZOMBIE_SPEED};
-use crate::resources::GameState;
+use crate::components::{
+    Backup, Bullet, BulletKind, Bungee, BungeePhase, Buttered, Chilled, Dancer, Fog, Grave,
+    GridCell, HealthBar, IceTrail, ImpCarrier, Lobbed, LobbedKind, Plant, PlantType,
+    SleepIndicator, TargetLayer, Tunneling, Vault, Zombie, ZombieAbility, ZombieKind, ZombiePart,
+    ZombieState,
+};
+use crate::constants::{
+    BACKUP_SLOTS, BULLET_SPEED, BUNGEE_DROP_TIME, BUNGEE_GRAB_TIME, BUNGEE_RISE_TIME,
+    BUTTER_CHANCE, CHILL_SPEED_FACTOR, COLOR_BUTTER, COLOR_CABBAGE, COLOR_ICE, COLOR_KERNEL,
+    COLOR_MELON, COLOR_SLEEP_INDICATOR, COLOR_WINTER_MELON, COLS, FOG_CLEAR_TIME, GRID_START_X,
+    GRID_START_Y, ICE_TRAIL_TIME, IMP_THROW_DISTANCE, LOB_ARC_HEIGHT, LOB_FLIGHT_TIME, PEA_DAMAGE,
+    PUFFSHROOM_RANGE, ROWS, SCAREDY_FEAR_RADIUS, SCREEN_WIDTH, STARFRUIT_ANGLES,
+    SUNSHROOM_GROW_TIME, TILE_SIZE,
+};
+use crate::events::{DamageEvent, Delivery, SpawnZombieEvent};
+use crate::resources::{GameState, Lawn, Level, Terrain};
 use bevy::prelude::*;
+use rand::Rng;
 
+#[allow(clippy::type_complexity)]
 pub fn plant_action(
     mut commands: Commands,
     time: Res<Time>,
     mut game_state: ResMut<GameState>,
-    mut query: Query<(&mut Plant, &Transform)>,
+    mut query: Query<(&mut Plant, &mut Transform, &GridCell), Without<Zombie>>,
+    zombie_query: Query<(&Transform, &Zombie, Has<Buttered>, Has<Chilled>), Without<Plant>>,
 ) {
-    for (mut plant, transform) in &mut query {
+    for (mut plant, mut transform, cell) in &mut query {
+        if plant.asleep {
+            continue;
+        }
         plant.timer.tick(time.delta());
+        plant.age += time.delta_seconds();
+
+        // Only zombies this plant's shots can reach count, Cactus spikes also go up to balloons
+        let shot = if plant.kind == PlantType::Cactus {
+            BulletKind::Spike
+        } else {
+            BulletKind::Pea
+        };
+
+        // Closest zombie ahead in this lane, as a distance
+        let row = cell.y;
+        let zombie_ahead = zombie_query
+            .iter()
+            .filter(|(z_transform, zombie, ..)| {
+                zombie.row == row
+                    && shot.hits(zombie.layer)
+                    && z_transform.translation.x > transform.translation.x
+            })
+            .map(|(z_transform, ..)| z_transform.translation.x - transform.translation.x)
+            .min_by(f32::total_cmp);
 
         match plant.kind {
             PlantType::Peashooter => {
                 if plant.timer.finished() {
-                    let spawn_pos = transform.translation + Vec3::new(40.0, 20.0, 3.0);
-                    commands.spawn((
+                    spawn_bullet(
+                        &mut commands,
+                        BulletKind::Pea,
+                        transform.translation,
+                        f32::INFINITY,
+                    );
+                }
+            }
+            PlantType::SnowPea => {
+                if plant.timer.finished() {
+                    spawn_bullet(
+                        &mut commands,
+                        BulletKind::FrozenPea,
+                        transform.translation,
+                        f32::INFINITY,
+                    );
+                }
+            }
+            PlantType::Repeater | PlantType::GatlingPea => {
+                if plant.timer.finished() {
+                    // A burst of peas, spaced back along the lane so they don't overlap
+                    let count = if plant.kind == PlantType::Repeater {
+                        2
+                    } else {
+                        4
+                    };
+                    for i in 0..count {
+                        spawn_bullet(
+                            &mut commands,
+                            BulletKind::Pea,
+                            transform.translation - Vec3::X * (i as f32 * 25.0),
+                            f32::INFINITY,
+                        );
+                    }
+                }
+            }
+            PlantType::SplitPea => {
+                if plant.timer.finished() {
+                    spawn_bullet(
+                        &mut commands,
+                        BulletKind::Pea,
+                        transform.translation,
+                        f32::INFINITY,
+                    );
+                    // Two peas out of the back snout
+                    for back_x in [-40.0, -65.0] {
+                        let offset = Vec3::new(back_x, 20.0, 3.0);
+                        spawn_projectile(
+                            &mut commands,
+                            BulletKind::Pea,
+                            transform.translation + offset,
+                            Vec2::NEG_X,
+                            f32::INFINITY,
+                        );
+                    }
+                }
+            }
+            PlantType::PuffShroom => {
+                if plant.timer.finished()
+                    && zombie_ahead.is_some_and(|dist| dist <= PUFFSHROOM_RANGE)
+                {
+                    spawn_bullet(
+                        &mut commands,
+                        BulletKind::Spore,
+                        transform.translation,
+                        PUFFSHROOM_RANGE,
+                    );
+                }
+            }
+            PlantType::ScaredyShroom => {
+                // Ducks down while any zombie is close, and doesn't shoot from there
+                let plant_pos = transform.translation.truncate();
+                let scared = zombie_query.iter().any(|(z_transform, zombie, ..)| {
+                    shot.hits(zombie.layer)
+                        && z_transform.translation.truncate().distance(plant_pos)
+                            < SCAREDY_FEAR_RADIUS
+                });
+                transform.scale.y = if scared { 0.5 } else { 1.0 };
+
+                if plant.timer.finished() && !scared && zombie_ahead.is_some() {
+                    spawn_bullet(
+                        &mut commands,
+                        BulletKind::Spore,
+                        transform.translation,
+                        f32::INFINITY,
+                    );
+                }
+            }
+            PlantType::Cactus => {
+                if plant.timer.finished() {
+                    spawn_bullet(
+                        &mut commands,
+                        BulletKind::Spike,
+                        transform.translation,
+                        f32::INFINITY,
+                    );
+                }
+            }
+            PlantType::Starfruit => {
+                // Fires as soon as anything is on the lawn, stars cover several lanes
+                let lawn_right = (COLS as f32).mul_add(TILE_SIZE, GRID_START_X);
+                let zombie_on_lawn = zombie_query.iter().any(|(z_transform, zombie, ..)| {
+                    shot.hits(zombie.layer) && z_transform.translation.x < lawn_right
+                });
+                if plant.timer.finished() && zombie_on_lawn {
+                    for angle in STARFRUIT_ANGLES {
+                        let direction = Vec2::from_angle(angle.to_radians());
+                        spawn_projectile(
+                            &mut commands,
+                            BulletKind::Star,
+                            transform.translation + (direction * 20.0).extend(3.0),
+                            direction,
+                            f32::INFINITY,
+                        );
+                    }
+                }
+            }
+            PlantType::SunShroom => {
+                let grown = plant.age >= SUNSHROOM_GROW_TIME;
+                if grown {
+                    transform.scale = Vec3::ONE;
+                }
+                if plant.timer.finished() {
+                    game_state.sun += if grown { 25 } else { 15 };
+                }
+            }
+            PlantType::Sunflower => {
+                if plant.timer.finished() {
+                    game_state.sun += 25;
+                }
+            }
+            PlantType::TwinSunflower => {
+                if plant.timer.finished() {
+                    game_state.sun += 50;
+                }
+            }
+            PlantType::PotatoMine => {
+                if plant.timer.finished() && !plant.armed {
+                    plant.armed = true;
+                }
+            }
+            PlantType::CabbagePult
+            | PlantType::KernelPult
+            | PlantType::MelonPult
+            | PlantType::WinterMelon => {
+                if !plant.timer.finished() {
+                    continue;
+                }
+
+                // Nearest zombie ahead in the lane, aimed where it will be on landing
+                let Some(target) = zombie_query
+                    .iter()
+                    .filter(|(z_transform, zombie, ..)| {
+                        zombie.row == row
+                            && zombie.layer == TargetLayer::Ground
+                            && z_transform.translation.x > transform.translation.x
+                    })
+                    .min_by(|(a, ..), (b, ..)| a.translation.x.total_cmp(&b.translation.x))
+                    .map(|(z_transform, zombie, buttered, chilled)| {
+                        let mut predicted = z_transform.translation.truncate();
+                        if zombie.state == ZombieState::Walking && !buttered {
+                            let speed = if chilled {
+                                zombie.speed * CHILL_SPEED_FACTOR
+                            } else {
+                                zombie.speed
+                            };
+                            predicted.x += speed * zombie.direction * LOB_FLIGHT_TIME;
+                        }
+                        predicted
+                    })
+                else {
+                    continue;
+                };
+
+                let kind = match plant.kind {
+                    PlantType::CabbagePult => LobbedKind::Cabbage,
+                    PlantType::MelonPult => LobbedKind::Melon,
+                    PlantType::WinterMelon => LobbedKind::WinterMelon,
+                    _ if rand::thread_rng().gen_bool(BUTTER_CHANCE) => LobbedKind::Butter,
+                    _ => LobbedKind::Kernel,
+                };
+                let color = match kind {
+                    LobbedKind::Cabbage => COLOR_CABBAGE,
+                    LobbedKind::Kernel => COLOR_KERNEL,
+                    LobbedKind::Butter => COLOR_BUTTER,
+                    LobbedKind::Melon => COLOR_MELON,
+                    LobbedKind::WinterMelon => COLOR_WINTER_MELON,
+                };
+
+                let start = transform.translation.truncate() + Vec2::new(-15.0, 20.0);
+                commands.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color,
+                            custom_size: Some(Vec2::new(15.0, 15.0)),
+                            ..default()
+                        },
+                        transform: Transform::from_translation(start.extend(3.0)),
+                        ..default()
+                    },
+                    Lobbed {
+                        kind,
+                        start,
+                        target,
+                        row,
+                        timer: Timer::from_seconds(LOB_FLIGHT_TIME, TimerMode::Once),
+                    },
+                ));
+            }
+            // Spikeweed damage is dealt in combat on each timer tick, Torchwood works on
+            // passing peas there too
+            PlantType::WallNut
+            | PlantType::TallNut
+            | PlantType::Pumpkin
+            | PlantType::LilyPad
+            | PlantType::Spikeweed
+            | PlantType::Torchwood
+            | PlantType::Squash
+            | PlantType::FumeShroom
+            | PlantType::MagnetShroom
+            | PlantType::Garlic
+            | PlantType::Blover
+            | PlantType::GraveBuster
+            | PlantType::UmbrellaLeaf
+            | PlantType::CoffeeBean => {} // Fumes are dealt in combat, Coffee Bean wakes its cell
+        }
+    }
+}
+
+// Peas leave from the shooter's snout, straight down the lane
+fn spawn_bullet(commands: &mut Commands, kind: BulletKind, plant_pos: Vec3, range: f32) {
+    spawn_projectile(
+        commands,
+        kind,
+        plant_pos + Vec3::new(40.0, 20.0, 3.0),
+        Vec2::X,
+        range,
+    );
+}
+
+fn spawn_projectile(
+    commands: &mut Commands,
+    kind: BulletKind,
+    pos: Vec3,
+    direction: Vec2,
+    range: f32,
+) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: kind.color(),
+                custom_size: Some(kind.size()),
+                ..default()
+            },
+            transform: Transform::from_translation(pos)
+                .with_rotation(Quat::from_rotation_z(direction.to_angle())),
+            ..default()
+        },
+        Bullet {
+            kind,
+            velocity: direction * BULLET_SPEED,
+            damage: PEA_DAMAGE,
+            torchwood: None,
+            range,
+        },
+    ));
+}
+
+// Parabolic flight from the pult to the landing point; landing is resolved in combat
+pub fn move_lobbed(time: Res<Time>, mut query: Query<(&mut Transform, &mut Lobbed)>) {
+    for (mut transform, mut lobbed) in &mut query {
+        lobbed.timer.tick(time.delta());
+        let t = lobbed.timer.fraction();
+        let ground = lobbed.start.lerp(lobbed.target, t);
+        let height = LOB_ARC_HEIGHT * 4.0 * t * (1.0 - t);
+        transform.translation.x = ground.x;
+        transform.translation.y = ground.y + height;
+    }
+}
+
+// A finished Coffee Bean wakes the mushroom under it and is used up
+pub fn coffee_bean_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Plant, &GridCell)>,
+) {
+    let woken_cells: Vec<(i32, i32)> = query
+        .iter()
+        .filter(|(_, plant, _)| plant.kind == PlantType::CoffeeBean && plant.timer.finished())
+        .map(|(entity, _, cell)| {
+            commands.entity(entity).despawn_recursive();
+            (cell.x, cell.y)
+        })
+        .collect();
+
+    for (_, mut plant, cell) in &mut query {
+        if woken_cells.contains(&(cell.x, cell.y)) && plant.kind.is_mushroom() {
+            plant.asleep = false;
+        }
+    }
+}
+
+// Blover blows the fog and any balloons away once its gust is done, then leaves
+pub fn blover_system(
+    mut commands: Commands,
+    query: Query<(Entity, &Plant)>,
+    zombie_query: Query<(Entity, &Zombie)>,
+    mut level: ResMut<Level>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    for (entity, plant) in query.iter() {
+        if plant.kind == PlantType::Blover && plant.timer.finished() {
+            level.fog_cleared = FOG_CLEAR_TIME;
+            // Anything in the air is blown off the lawn, through the damage system like any
+            // other kill so nothing still queued for the zombie this frame is left dangling
+            for (zombie_entity, zombie) in zombie_query.iter() {
+                if zombie.layer == TargetLayer::Air {
+                    damage_events.send(DamageEvent {
+                        zombie: zombie_entity,
+                        amount: zombie.health,
+                        delivery: Delivery::Area,
+                        direction: Vec2::ZERO,
+                    });
+                }
+            }
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+// A Grave Buster that finished eating takes its grave with it
+pub fn grave_buster_system(
+    mut commands: Commands,
+    plant_query: Query<(Entity, &Plant, &GridCell)>,
+    grave_query: Query<(Entity, &GridCell), With<Grave>>,
+) {
+    for (entity, plant, cell) in plant_query.iter() {
+        if plant.kind != PlantType::GraveBuster || !plant.timer.finished() {
+            continue;
+        }
+        for (grave, grave_cell) in grave_query.iter() {
+            if grave_cell.x == cell.x && grave_cell.y == cell.y {
+                commands.entity(grave).despawn_recursive();
+            }
+        }
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// Zombonis ice over every grass cell they drive across, the ice melts cell by cell
+pub fn ice_trail_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut lawn: ResMut<Lawn>,
+    zombie_query: Query<(&Transform, &Zombie)>,
+    ice_query: Query<(Entity, &GridCell), With<IceTrail>>,
+) {
+    for row in &mut lawn.ice {
+        for ice in row {
+            *ice = (*ice - time.delta_seconds()).max(0.0);
+        }
+    }
+
+    for (transform, zombie) in zombie_query.iter() {
+        if zombie.kind.stats().ability != ZombieAbility::IceTrail {
+            continue;
+        }
+        let col = ((transform.translation.x - GRID_START_X) / TILE_SIZE).round() as i32;
+        let row = zombie.row;
+        if !(0..COLS).contains(&col) || lawn.terrain(col, row) != Terrain::Grass {
+            continue;
+        }
+
+        #[allow(clippy::cast_sign_loss)]
+        let ice = &mut lawn.ice[row as usize][col as usize];
+        *ice = ICE_TRAIL_TIME;
+        if ice_query
+            .iter()
+            .any(|(_, cell)| cell.x == col && cell.y == row)
+        {
+            continue;
+        }
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_ICE,
+                    custom_size: Some(Vec2::splat(TILE_SIZE)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(
+                    (col as f32).mul_add(TILE_SIZE, GRID_START_X),
+                    (row as f32).mul_add(TILE_SIZE, GRID_START_Y),
+                    0.5, // On the grass, under the plants
+                ),
+                ..default()
+            },
+            IceTrail,
+            GridCell { x: col, y: row },
+        ));
+    }
+
+    for (entity, cell) in ice_query.iter() {
+        if !lawn.iced(cell.x, cell.y) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+// Fog only covers the lawn on foggy levels, and not while a Blover's gust lasts
+pub fn fog_system(
+    time: Res<Time>,
+    mut level: ResMut<Level>,
+    mut fog_query: Query<&mut Visibility, With<Fog>>,
+) {
+    level.fog_cleared = (level.fog_cleared - time.delta_seconds()).max(0.0);
+    let foggy = level.fog && level.fog_cleared <= 0.0;
+
+    for mut visibility in &mut fog_query {
+        *visibility = if foggy {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+// Keeps a "Zzz" over every sleeping plant
+pub fn sleep_indicator_system(
+    mut commands: Commands,
+    plant_query: Query<(Entity, &Plant)>,
+    indicator_query: Query<(Entity, &Parent), With<SleepIndicator>>,
+) {
+    for (entity, plant) in plant_query.iter() {
+        let indicator = indicator_query
+            .iter()
+            .find(|(_, parent)| parent.get() == entity)
+            .map(|(indicator, _)| indicator);
+
+        match (plant.asleep, indicator) {
+            (true, None) => {
+                commands.entity(entity).with_children(|parent| {
+                    parent.spawn((
                         SpriteBundle {
                             sprite: Sprite {
-                                color: COLOR_BULLET,
-                                custom_size: Some(Vec2::new(15.0, 15.0)),
+                                color: COLOR_SLEEP_INDICATOR,
+                                custom_size: Some(Vec2::new(10.0, 10.0)),
                                 ..default()
                             },
-                            transform: Transform::from_translation(spawn_pos),
+                            transform: Transform::from_xyz(18.0, 30.0, 0.5),
                             ..default()
                         },
-                        Bullet,
+                        SleepIndicator,
                     ));
+                });
+            }
+            (false, Some(indicator)) => commands.entity(indicator).despawn_recursive(),
+            _ => {}
+        }
+    }
+}
+
+// Carries a vaulting or thrown zombie through the air in an arc, it walks on from where it lands
+pub fn vault_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Transform, &mut Zombie, &mut Vault)>,
+) {
+    for (entity, mut transform, mut zombie, mut vault) in &mut query {
+        vault.timer.tick(time.delta());
+        let t = vault.timer.fraction();
+        let ground = vault.start.lerp(vault.target, t);
+        let height = vault.height * (t * std::f32::consts::PI).sin();
+        transform.translation.x = ground.x;
+        transform.translation.y = ground.y + height;
+
+        if vault.timer.finished() {
+            zombie.state = ZombieState::Walking;
+            commands.entity(entity).remove::<Vault>();
+        } else {
+            zombie.state = ZombieState::Vaulting;
+        }
+    }
+}
+
+// Bungee zombies mark their cell, drop onto it and lift off the topmost plant there, unless
+// an Umbrella Leaf next to it bounces them back up first
+#[allow(clippy::type_complexity)]
+pub fn bungee_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut bungee_query: Query<(Entity, &mut Transform, &mut Zombie, &mut Bungee)>,
+    mut marker_query: Query<&mut Transform, (Without<Zombie>, Without<Plant>)>,
+    plant_query: Query<(Entity, &Plant, &GridCell)>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    for (entity, mut transform, mut zombie, mut bungee) in &mut bungee_query {
+        bungee.timer.tick(time.delta());
+        let t = bungee.timer.fraction();
+        let cell_y = (zombie.row as f32).mul_add(TILE_SIZE, GRID_START_Y);
+
+        match bungee.phase {
+            BungeePhase::Marking => {
+                if bungee.timer.finished() {
+                    bungee.phase = BungeePhase::Dropping;
+                    bungee.timer = Timer::from_seconds(BUNGEE_DROP_TIME, TimerMode::Once);
                 }
             }
-            PlantType::Sunflower => {
-                if plant.timer.finished() {
-                    game_state.sun += 25;
+            BungeePhase::Dropping => {
+                transform.translation.y = bungee.sky_y.lerp(cell_y, t);
+                if bungee.timer.finished() {
+                    let covered = plant_query.iter().any(|(_, plant, cell)| {
+                        plant.kind == PlantType::UmbrellaLeaf
+                            && (cell.x - bungee.col).abs() <= 1
+                            && (cell.y - zombie.row).abs() <= 1
+                    });
+                    if covered {
+                        bungee.phase = BungeePhase::Rising;
+                        bungee.timer = Timer::from_seconds(BUNGEE_RISE_TIME, TimerMode::Once);
+                    } else {
+                        zombie.layer = TargetLayer::Ground;
+                        bungee.phase = BungeePhase::Grabbing;
+                        bungee.timer = Timer::from_seconds(BUNGEE_GRAB_TIME, TimerMode::Once);
+                    }
                 }
             }
-            PlantType::PotatoMine => {
-                if plant.timer.finished() && !plant.armed {
-                    plant.armed = true;
-                    // Visual indication? For prototype, maybe just logic.
+            BungeePhase::Grabbing => {
+                if bungee.timer.finished() {
+                    // Taken off the grid right away, it's only a sprite on the cord from here
+                    let target = plant_query
+                        .iter()
+                        .filter(|(_, _, cell)| cell.x == bungee.col && cell.y == zombie.row)
+                        .max_by_key(|(_, plant, _)| plant.kind.layer());
+                    if let Some((plant_entity, ..)) = target {
+                        commands
+                            .entity(plant_entity)
+                            .remove::<(Plant, GridCell)>()
+                            .set_parent(entity)
+                            .insert(Transform::from_xyz(0.0, -40.0, 0.5));
+                    }
+                    commands.entity(bungee.marker).despawn_recursive();
+                    zombie.layer = TargetLayer::Air;
+                    bungee.phase = BungeePhase::Rising;
+                    bungee.timer = Timer::from_seconds(BUNGEE_RISE_TIME, TimerMode::Once);
+                }
+            }
+            BungeePhase::Rising => {
+                transform.translation.y = cell_y.lerp(bungee.sky_y, t);
+                // Gone off the top of the screen, it leaves through the damage system so it's
+                // removed with anything it carries once nothing else can still touch it
+                if bungee.timer.finished() {
+                    damage_events.send(DamageEvent {
+                        zombie: entity,
+                        amount: zombie.health,
+                        delivery: Delivery::Area,
+                        direction: Vec2::ZERO,
+                    });
+                    continue;
+                }
+            }
+        }
+
+        // Target stays on the cell while the cord moves
+        if let Ok(mut marker) = marker_query.get_mut(bungee.marker) {
+            marker.translation.y = cell_y - transform.translation.y;
+        }
+    }
+}
+
+// Gargantuars throw their Imp a few tiles ahead once they're down to half health
+pub fn gargantuar_system(
+    mut commands: Commands,
+    mut spawn_events: EventWriter<SpawnZombieEvent>,
+    mut query: Query<(&Transform, &Zombie, &mut ImpCarrier)>,
+) {
+    for (transform, zombie, mut carrier) in &mut query {
+        if zombie.health > zombie.kind.stats().health / 2.0 {
+            continue;
+        }
+        let Some(imp) = carrier.imp.take() else {
+            continue;
+        };
+
+        commands.entity(imp).despawn_recursive();
+        let landing = (transform.translation.x - IMP_THROW_DISTANCE).max(GRID_START_X);
+        spawn_events.send(SpawnZombieEvent {
+            kind: ZombieKind::Imp,
+            x: transform.translation.x,
+            row: zombie.row,
+            leader: None,
+            thrown_to: Some(landing),
+        });
+    }
+}
+
+// Dancing zombies stop on reaching the lawn, dance for a moment and call in Backup Dancers
+// for every empty slot around them, then repeat whenever one of them has died
+pub fn dancer_system(
+    time: Res<Time>,
+    mut spawn_events: EventWriter<SpawnZombieEvent>,
+    mut dancer_query: Query<(Entity, &Transform, &mut Zombie, &mut Dancer)>,
+    backup_query: Query<&Backup>,
+) {
+    let lawn_edge = ((COLS - 1) as f32).mul_add(TILE_SIZE, GRID_START_X);
+
+    for (entity, transform, mut zombie, mut dancer) in &mut dancer_query {
+        let free_slots: Vec<usize> = (0..BACKUP_SLOTS.len())
+            .filter(|slot| {
+                !backup_query
+                    .iter()
+                    .any(|backup| backup.leader == entity && backup.slot == *slot)
+            })
+            .filter(|slot| (0..ROWS).contains(&(zombie.row + BACKUP_SLOTS[*slot].1)))
+            .collect();
+
+        if !dancer.entered {
+            if transform.translation.x > lawn_edge {
+                continue;
+            }
+            dancer.entered = true;
+            zombie.state = ZombieState::Dancing;
+        }
+
+        dancer.resummon.tick(time.delta());
+        if zombie.state == ZombieState::Walking
+            && dancer.resummon.just_finished()
+            && !free_slots.is_empty()
+        {
+            zombie.state = ZombieState::Dancing;
+            dancer.pause.reset();
+        }
+
+        if zombie.state != ZombieState::Dancing {
+            continue;
+        }
+        dancer.pause.tick(time.delta());
+        if !dancer.pause.finished() {
+            continue;
+        }
+
+        for slot in free_slots {
+            let (dx, drow) = BACKUP_SLOTS[slot];
+            spawn_events.send(SpawnZombieEvent {
+                kind: ZombieKind::BackupDancer,
+                x: transform.translation.x + dx,
+                row: zombie.row + drow,
+                leader: Some((entity, slot)),
+                thrown_to: None,
+            });
+        }
+        zombie.state = ZombieState::Walking;
+    }
+}
+
+// Diggers stay hidden under a dirt mound until they reach the house end of the lawn, then
+// surface and turn around to walk back, eating plants from behind
+pub fn digger_system(
+    mut commands: Commands,
+    mut digger_query: Query<(Entity, &mut Transform, &mut Zombie, &Tunneling, &Children)>,
+    mut visibility_query: Query<&mut Visibility, With<ZombiePart>>,
+    mut bar_query: Query<&mut Transform, (With<HealthBar>, Without<Zombie>)>,
+) {
+    for (entity, mut transform, mut zombie, tunneling, children) in &mut digger_query {
+        let surfaced = transform.translation.x <= GRID_START_X;
+        // Only the body is hidden, the mound and health bar stay
+        for &child in children {
+            if let Ok(mut visibility) = visibility_query.get_mut(child) {
+                *visibility = if surfaced {
+                    Visibility::Inherited
+                } else {
+                    Visibility::Hidden
+                };
+            }
+        }
+
+        if surfaced {
+            commands.entity(tunneling.mound).despawn_recursive();
+            commands.entity(entity).remove::<Tunneling>();
+            zombie.direction = 1.0;
+            zombie.layer = TargetLayer::Ground;
+            zombie.speed = zombie.kind.stats().walk_speed;
+            // Mirror the sprites so it faces the way it walks, but keep the health bar the
+            // right way round so it still drains towards the left
+            transform.scale.x = -transform.scale.x;
+            for &child in children {
+                if let Ok(mut bar) = bar_query.get_mut(child) {
+                    bar.scale.x = -bar.scale.x;
                 }
             }
-            PlantType::WallNut => {}
+        }
+    }
+}
+
+// Counts down seed packets that are recharging, freeing them when done
+pub fn recharge_system(time: Res<Time>, mut game_state: ResMut<GameState>) {
+    game_state.recharging.retain(|_, timer| {
+        timer.tick(time.delta());
+        !timer.finished()
+    });
+}
+
+// Wears off butter and chill, removing their overlay sprites
+pub fn status_effect_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut butter_query: Query<(Entity, &mut Buttered)>,
+    mut chill_query: Query<(Entity, &mut Chilled)>,
+) {
+    for (entity, mut buttered) in &mut butter_query {
+        buttered.timer.tick(time.delta());
+        if buttered.timer.finished() {
+            commands.entity(buttered.splat).despawn_recursive();
+            commands.entity(entity).remove::<Buttered>();
+        }
+    }
+
+    for (entity, mut chilled) in &mut chill_query {
+        chilled.timer.tick(time.delta());
+        if chilled.timer.finished() {
+            commands.entity(chilled.tint).despawn_recursive();
+            commands.entity(entity).remove::<Chilled>();
         }
     }
 }
@@ -49,27 +780,74 @@ pub fn plant_action(
 pub fn move_bullets(
     mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(Entity, &mut Transform), With<Bullet>>,
+    mut query: Query<(Entity, &mut Transform, &mut Bullet)>,
 ) {
-    for (entity, mut transform) in &mut query {
-        transform.translation.x += BULLET_SPEED * time.delta_seconds();
-        if transform.translation.x > SCREEN_WIDTH / 2.0 {
+    // Lawn plus a tile of margin on every side
+    let bounds = Rect::new(
+        1.5f32.mul_add(-TILE_SIZE, GRID_START_X),
+        1.5f32.mul_add(-TILE_SIZE, GRID_START_Y),
+        (COLS as f32 + 0.5).mul_add(TILE_SIZE, GRID_START_X),
+        (ROWS as f32 + 0.5).mul_add(TILE_SIZE, GRID_START_Y),
+    );
+
+    for (entity, mut transform, mut bullet) in &mut query {
+        let step = bullet.velocity * time.delta_seconds();
+        transform.translation += step.extend(0.0);
+        bullet.range -= step.length();
+        if !bounds.contains(transform.translation.truncate()) || bullet.range <= 0.0 {
             commands.entity(entity).despawn();
         }
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn move_zombies(
     mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(Entity, &mut Transform, &Zombie)>,
+    mut query: Query<(
+        Entity,
+        &mut Transform,
+        &mut Zombie,
+        Has<Buttered>,
+        Has<Chilled>,
+    )>,
 ) {
-    for (entity, mut transform, zombie) in &mut query {
-        if zombie.state == ZombieState::Walking {
-            transform.translation.x -= ZOMBIE_SPEED * time.delta_seconds();
+    for (entity, mut transform, mut zombie, buttered, chilled) in &mut query {
+        let speed = if chilled {
+            zombie.speed * CHILL_SPEED_FACTOR
+        } else {
+            zombie.speed
+        };
+        let step = speed * time.delta_seconds();
+
+        if !buttered {
+            match zombie.state {
+                ZombieState::Walking => transform.translation.x += step * zombie.direction,
+                ZombieState::ChangingLane(row) => {
+                    // Diagonal: forward and across at the same pace. It counts as being in the
+                    // new lane once it's past the midpoint between the two
+                    let target_y = (row as f32).mul_add(TILE_SIZE, GRID_START_Y);
+                    let dy = target_y - transform.translation.y;
+                    transform.translation.x += step * zombie.direction;
+                    if dy.abs() <= step {
+                        transform.translation.y = target_y;
+                        zombie.state = ZombieState::Walking;
+                    } else {
+                        transform.translation.y += step * dy.signum();
+                    }
+                    if dy.abs() <= TILE_SIZE / 2.0 {
+                        zombie.row = row;
+                    }
+                }
+                ZombieState::Eating(_)
+                | ZombieState::Vaulting
+                | ZombieState::Dancing
+                | ZombieState::Hanging => {}
+            }
         }
 
-        if transform.translation.x < -SCREEN_WIDTH / 2.0 - 50.0 {
+        // Off whichever edge it's walking towards
+        if transform.translation.x * zombie.direction > SCREEN_WIDTH / 2.0 + 50.0 {
             commands.entity(entity).despawn_recursive();
         }
     }
@@ -1,10 +1,15 @@
-use crate::components::{Cursor, GridCell, Plant, PlantType, Tool, ToolButton};
+use crate::components::{Cursor, Grave, GridCell, Plant, PlantLayer, PlantType, Tool, ToolButton};
 use crate::constants::{
-    COLS, COST_PEASHOOTER, COST_POTATOMINE, COST_SUNFLOWER, COST_WALLNUT, ROWS, SCREEN_WIDTH,
-    TILE_SIZE,
+    COLS, COST_BLOVER, COST_CABBAGEPULT, COST_CACTUS, COST_COFFEEBEAN, COST_FUMESHROOM,
+    COST_GARLIC, COST_GATLINGPEA, COST_GRAVEBUSTER, COST_KERNELPULT, COST_LILYPAD,
+    COST_MAGNETSHROOM, COST_MELONPULT, COST_PEASHOOTER, COST_POTATOMINE, COST_PUFFSHROOM,
+    COST_PUMPKIN, COST_REPEATER, COST_SCAREDYSHROOM, COST_SNOWPEA, COST_SPIKEWEED, COST_SPLITPEA,
+    COST_SQUASH, COST_STARFRUIT, COST_SUNFLOWER, COST_SUNSHROOM, COST_TALLNUT, COST_TORCHWOOD,
+    COST_TWINSUNFLOWER, COST_UMBRELLALEAF, COST_WALLNUT, COST_WINTERMELON, GRID_START_X,
+    GRID_START_Y, ROWS, TILE_SIZE,
 };
-use crate::resources::GameState;
-use crate::systems::spawning::{spawn_plant, spawn_plant_visuals};
+use crate::resources::{GameState, Lawn, Level, Settings, Terrain, TimeOfDay};
+use crate::systems::spawning::{spawn_plant, spawn_plant_visuals, upgrade_plant};
 use bevy::prelude::*;
 
 #[allow(clippy::type_complexity)]
@@ -127,13 +132,17 @@ pub fn cursor_system(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn input_system(
     mut commands: Commands,
     mouse: Res<ButtonInput<MouseButton>>,
     windows: Query<&Window>,
     camera_q: Query<(&Camera, &GlobalTransform)>,
     mut game_state: ResMut<GameState>,
-    existing_plants: Query<(Entity, &GridCell), With<Plant>>,
+    lawn: Res<Lawn>,
+    level: Res<Level>,
+    existing_plants: Query<(Entity, &GridCell, &Plant)>,
+    graves: Query<&GridCell, With<Grave>>,
 ) {
     if mouse.just_pressed(MouseButton::Left) {
         let Ok((camera, camera_transform)) = camera_q.get_single() else {
@@ -148,28 +157,66 @@ pub fn input_system(
             .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor))
             .map(|ray| ray.origin.truncate())
         {
-            // Grid geometry
-            // let grid_width_px = COLS as f32 * TILE_SIZE; // Unused
-            let grid_height_px = ROWS as f32 * TILE_SIZE;
-            let start_x = -SCREEN_WIDTH / 2.0 + TILE_SIZE / 2.0 + 50.0;
-            let start_y = -grid_height_px / 2.0 + TILE_SIZE / 2.0;
-
             // Manual inverse mapping due to start_x offset
-            let grid_base_x = start_x - TILE_SIZE / 2.0;
-            let grid_base_y = start_y - TILE_SIZE / 2.0;
+            let grid_base_x = GRID_START_X - TILE_SIZE / 2.0;
+            let grid_base_y = GRID_START_Y - TILE_SIZE / 2.0;
 
             let col = ((world_position.x - grid_base_x) / TILE_SIZE).floor() as i32;
             let row = ((world_position.y - grid_base_y) / TILE_SIZE).floor() as i32;
 
             if (0..COLS).contains(&col) && (0..ROWS).contains(&row) {
-                // Check occupancy
-                let occupied_plant = existing_plants
+                // Check occupancy, one plant per layer slot
+                let cell_plants: Vec<(Entity, &Plant)> = existing_plants
                     .iter()
-                    .find(|(_, cell)| cell.x == col && cell.y == row);
+                    .filter(|(_, cell, _)| cell.x == col && cell.y == row)
+                    .map(|(entity, _, plant)| (entity, plant))
+                    .collect();
+                let has_layer = |layer| cell_plants.iter().any(|(_, p)| p.kind.layer() == layer);
 
                 match game_state.selected_tool {
                     Tool::Plant(plant_type) => {
-                        if occupied_plant.is_some() {
+                        if game_state.recharging.contains_key(&plant_type) {
+                            return;
+                        }
+
+                        // Upgrades go on top of their base plant instead of an empty slot
+                        let upgrade_target = match plant_type.upgrade_base() {
+                            Some(base) => {
+                                let Some(&target) =
+                                    cell_plants.iter().find(|(_, p)| p.kind == base)
+                                else {
+                                    return;
+                                };
+                                Some(target)
+                            }
+                            None => None,
+                        };
+
+                        let layer = plant_type.layer();
+                        let on_grave = graves.iter().any(|grave| grave.x == col && grave.y == row);
+                        let placeable = match plant_type {
+                            // Nothing grows on a Zomboni's ice until it melts
+                            _ if lawn.iced(col, row) => false,
+                            PlantType::GraveBuster => on_grave && !has_layer(layer),
+                            _ => !on_grave && (upgrade_target.is_some() || !has_layer(layer)),
+                        };
+                        if !placeable {
+                            return;
+                        }
+
+                        // Lily Pads only float on water, everything else needs one there
+                        let on_water = lawn.terrain(col, row) == Terrain::Water;
+                        let supported = match layer {
+                            PlantLayer::Base => on_water,
+                            PlantLayer::Main | PlantLayer::Shell => {
+                                !on_water || has_layer(PlantLayer::Base)
+                            }
+                            // Coffee Bean only goes on a sleeping mushroom
+                            PlantLayer::Top => cell_plants
+                                .iter()
+                                .any(|(_, p)| p.kind.layer() == PlantLayer::Main && p.asleep),
+                        };
+                        if !supported {
                             return;
                         }
 
@@ -179,19 +226,63 @@ pub fn input_system(
                             PlantType::Sunflower => COST_SUNFLOWER,
                             PlantType::WallNut => COST_WALLNUT,
                             PlantType::PotatoMine => COST_POTATOMINE,
+                            PlantType::CabbagePult => COST_CABBAGEPULT,
+                            PlantType::KernelPult => COST_KERNELPULT,
+                            PlantType::Spikeweed => COST_SPIKEWEED,
+                            PlantType::SnowPea => COST_SNOWPEA,
+                            PlantType::Torchwood => COST_TORCHWOOD,
+                            PlantType::TallNut => COST_TALLNUT,
+                            PlantType::Pumpkin => COST_PUMPKIN,
+                            PlantType::LilyPad => COST_LILYPAD,
+                            PlantType::Squash => COST_SQUASH,
+                            PlantType::PuffShroom => COST_PUFFSHROOM,
+                            PlantType::SunShroom => COST_SUNSHROOM,
+                            PlantType::FumeShroom => COST_FUMESHROOM,
+                            PlantType::ScaredyShroom => COST_SCAREDYSHROOM,
+                            PlantType::CoffeeBean => COST_COFFEEBEAN,
+                            PlantType::Starfruit => COST_STARFRUIT,
+                            PlantType::Cactus => COST_CACTUS,
+                            PlantType::Repeater => COST_REPEATER,
+                            PlantType::MelonPult => COST_MELONPULT,
+                            PlantType::GatlingPea => COST_GATLINGPEA,
+                            PlantType::TwinSunflower => COST_TWINSUNFLOWER,
+                            PlantType::WinterMelon => COST_WINTERMELON,
+                            PlantType::MagnetShroom => COST_MAGNETSHROOM,
+                            PlantType::Garlic => COST_GARLIC,
+                            PlantType::Blover => COST_BLOVER,
+                            PlantType::GraveBuster => COST_GRAVEBUSTER,
+                            PlantType::SplitPea => COST_SPLITPEA,
+                            PlantType::UmbrellaLeaf => COST_UMBRELLALEAF,
                         };
 
                         if game_state.sun >= cost {
                             game_state.sun -= cost;
+                            if plant_type.recharge() > 0.0 {
+                                game_state.recharging.insert(
+                                    plant_type,
+                                    Timer::from_seconds(plant_type.recharge(), TimerMode::Once),
+                                );
+                            }
+
+                            if let Some((entity, base)) = upgrade_target {
+                                upgrade_plant(&mut commands, entity, base, plant_type);
+                                return;
+                            }
+
                             // Center of cell
-                            let pos_x = (col as f32).mul_add(TILE_SIZE, start_x);
-                            let pos_y = (row as f32).mul_add(TILE_SIZE, start_y);
-                            spawn_plant(&mut commands, plant_type, pos_x, pos_y, col, row);
+                            let pos_x = (col as f32).mul_add(TILE_SIZE, GRID_START_X);
+                            let pos_y = (row as f32).mul_add(TILE_SIZE, GRID_START_Y);
+                            let asleep =
+                                plant_type.is_mushroom() && level.time_of_day == TimeOfDay::Day;
+                            spawn_plant(&mut commands, plant_type, pos_x, pos_y, col, row, asleep);
                         }
                     }
                     Tool::Shovel => {
-                        if let Some((entity, _)) = occupied_plant {
-                            commands.entity(entity).despawn_recursive();
+                        // Top layer comes off first
+                        if let Some((entity, _)) =
+                            cell_plants.iter().max_by_key(|(_, p)| p.kind.layer())
+                        {
+                            commands.entity(*entity).despawn_recursive();
                         }
                     }
                 }
@@ -199,3 +290,10 @@ pub fn input_system(
         }
     }
 }
+
+// H toggles the health bars and damage numbers
+pub fn settings_system(keys: Res<ButtonInput<KeyCode>>, mut settings: ResMut<Settings>) {
+    if keys.just_pressed(KeyCode::KeyH) {
+        settings.show_health = !settings.show_health;
+    }
+}
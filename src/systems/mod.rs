@@ -1,3 +1,4 @@
+pub mod animation;
 pub mod combat;
 pub mod gameplay;
 pub mod input;
@@ -1,27 +1,45 @@
-use crate::components::{PlantType, SunText, Tool, ToolButton};
+use crate::components::{
+    Fog, Grave, GridCell, PlantType, RechargeOverlay, SunText, Tool, ToolButton,
+};
 use crate::constants::{
-    COLOR_GRASS_1, COLOR_GRASS_2, COLOR_PEASHOOTER_HEAD, COLOR_POTATOMINE_BODY,
-    COLOR_SUNFLOWER_PETALS, COLOR_SUN_TEXT, COLOR_WALLNUT_BODY, COLS, COST_PEASHOOTER,
-    COST_POTATOMINE, COST_SUNFLOWER, COST_WALLNUT, ROWS, SCREEN_WIDTH, TILE_SIZE,
+    COLOR_BLOVER, COLOR_CABBAGE, COLOR_CACTUS, COLOR_COFFEEBEAN, COLOR_FOG, COLOR_FUMESHROOM_CAP,
+    COLOR_GARLIC, COLOR_GATLING_HEAD, COLOR_GRASS_1, COLOR_GRASS_2, COLOR_GRAVE,
+    COLOR_GRAVE_BUSTER, COLOR_KERNEL, COLOR_LILYPAD, COLOR_MAGNETSHROOM_CAP, COLOR_MELON,
+    COLOR_NIGHT_SHADE, COLOR_PEASHOOTER_HEAD, COLOR_POTATOMINE_BODY, COLOR_PUFFSHROOM_CAP,
+    COLOR_PUMPKIN, COLOR_RECHARGE_OVERLAY, COLOR_REPEATER_HEAD, COLOR_SCAREDYSHROOM_CAP,
+    COLOR_SNOWPEA_HEAD, COLOR_SPIKEWEED_BASE, COLOR_SPLITPEA_HEAD, COLOR_SQUASH_BODY,
+    COLOR_STARFRUIT, COLOR_SUNFLOWER_PETALS, COLOR_SUNSHROOM_CAP, COLOR_SUN_TEXT,
+    COLOR_TALLNUT_BODY, COLOR_TORCHWOOD_FIRE, COLOR_UMBRELLA_LEAF, COLOR_WALLNUT_BODY,
+    COLOR_WATER_1, COLOR_WATER_2, COLOR_WINTER_MELON, COLS, COST_BLOVER, COST_CABBAGEPULT,
+    COST_CACTUS, COST_COFFEEBEAN, COST_FUMESHROOM, COST_GARLIC, COST_GATLINGPEA, COST_GRAVEBUSTER,
+    COST_KERNELPULT, COST_LILYPAD, COST_MAGNETSHROOM, COST_MELONPULT, COST_PEASHOOTER,
+    COST_POTATOMINE, COST_PUFFSHROOM, COST_PUMPKIN, COST_REPEATER, COST_SCAREDYSHROOM,
+    COST_SNOWPEA, COST_SPIKEWEED, COST_SPLITPEA, COST_SQUASH, COST_STARFRUIT, COST_SUNFLOWER,
+    COST_SUNSHROOM, COST_TALLNUT, COST_TORCHWOOD, COST_TWINSUNFLOWER, COST_UMBRELLALEAF,
+    COST_WALLNUT, COST_WINTERMELON, FOG_COLUMNS, GRAVE_DEPTH, GRID_START_X, GRID_START_Y,
+    OVERLAY_DEPTH, ROWS, SEED_PACKET_COLUMNS, SEED_PACKET_GAP, SEED_PACKET_HEIGHT,
+    SEED_PACKET_WIDTH, TILE_SIZE,
 };
+use crate::resources::{Lawn, Level, Terrain, TimeOfDay};
+use crate::systems::animation::row_depth;
 use bevy::prelude::*;
+use rand::Rng;
 
-pub fn setup(mut commands: Commands) {
+pub fn setup(mut commands: Commands, lawn: Res<Lawn>, level: Res<Level>) {
     // Camera
     commands.spawn(Camera2dBundle::default());
 
     // Grid Background (Left side)
-    // let grid_width_px = COLS as f32 * TILE_SIZE; // Unused
-    let grid_height_px = ROWS as f32 * TILE_SIZE;
-    let start_x = -SCREEN_WIDTH / 2.0 + TILE_SIZE / 2.0 + 50.0; // Left align with margin
-    let start_y = -grid_height_px / 2.0 + TILE_SIZE / 2.0;
-
     for row in 0..ROWS {
         for col in 0..COLS {
+            let (color_1, color_2) = match lawn.terrain(col, row) {
+                Terrain::Grass => (COLOR_GRASS_1, COLOR_GRASS_2),
+                Terrain::Water => (COLOR_WATER_1, COLOR_WATER_2),
+            };
             let color = if (row + col) % 2 == 0 {
-                COLOR_GRASS_1
+                color_1
             } else {
-                COLOR_GRASS_2
+                color_2
             };
 
             commands.spawn(SpriteBundle {
@@ -31,8 +49,8 @@ pub fn setup(mut commands: Commands) {
                     ..default()
                 },
                 transform: Transform::from_xyz(
-                    (col as f32).mul_add(TILE_SIZE, start_x),
-                    (row as f32).mul_add(TILE_SIZE, start_y),
+                    (col as f32).mul_add(TILE_SIZE, GRID_START_X),
+                    (row as f32).mul_add(TILE_SIZE, GRID_START_Y),
                     0.0,
                 ),
                 ..default()
@@ -40,6 +58,74 @@ pub fn setup(mut commands: Commands) {
         }
     }
 
+    // Night shade over the whole lawn on night levels
+    if level.time_of_day == TimeOfDay::Night {
+        commands.spawn(SpriteBundle {
+            sprite: Sprite {
+                color: COLOR_NIGHT_SHADE,
+                custom_size: Some(Vec2::new(COLS as f32 * TILE_SIZE, ROWS as f32 * TILE_SIZE)),
+                ..default()
+            },
+            transform: Transform::from_xyz(
+                ((COLS - 1) as f32).mul_add(TILE_SIZE / 2.0, GRID_START_X),
+                ((ROWS - 1) as f32).mul_add(TILE_SIZE / 2.0, GRID_START_Y),
+                0.1,
+            ),
+            ..default()
+        });
+    }
+
+    // Fog over the rightmost columns, shown by the fog system on foggy levels
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: COLOR_FOG,
+                custom_size: Some(Vec2::new(
+                    FOG_COLUMNS as f32 * TILE_SIZE,
+                    ROWS as f32 * TILE_SIZE,
+                )),
+                ..default()
+            },
+            transform: Transform::from_xyz(
+                ((2 * COLS - FOG_COLUMNS - 1) as f32).mul_add(TILE_SIZE / 2.0, GRID_START_X),
+                ((ROWS - 1) as f32).mul_add(TILE_SIZE / 2.0, GRID_START_Y),
+                OVERLAY_DEPTH, // Over plants, zombies and projectiles alike
+            ),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        Fog,
+    ));
+
+    // Graves on random grass cells in the back half of the lawn, on levels that have them
+    let mut rng = rand::thread_rng();
+    let mut grave_cells: Vec<(i32, i32)> = Vec::new();
+    while grave_cells.len() < level.graves {
+        let cell = (rng.gen_range(COLS / 2..COLS), rng.gen_range(0..ROWS));
+        if lawn.terrain(cell.0, cell.1) == Terrain::Grass && !grave_cells.contains(&cell) {
+            grave_cells.push(cell);
+        }
+    }
+    for (col, row) in grave_cells {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_GRAVE,
+                    custom_size: Some(Vec2::new(40.0, 55.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(
+                    (col as f32).mul_add(TILE_SIZE, GRID_START_X),
+                    (row as f32).mul_add(TILE_SIZE, GRID_START_Y) - 5.0,
+                    row_depth(row, GRAVE_DEPTH),
+                ),
+                ..default()
+            },
+            Grave,
+            GridCell { x: col, y: row },
+        ));
+    }
+
     // UI Panel (Right side)
     // let panel_x = SCREEN_WIDTH / 2.0 - 90.0; // Unused
 
@@ -83,17 +169,135 @@ pub fn setup(mut commands: Commands) {
             COST_POTATOMINE,
             COLOR_POTATOMINE_BODY,
         ),
+        (
+            PlantType::CabbagePult,
+            "Cabbage",
+            COST_CABBAGEPULT,
+            COLOR_CABBAGE,
+        ),
+        (
+            PlantType::KernelPult,
+            "Kernel",
+            COST_KERNELPULT,
+            COLOR_KERNEL,
+        ),
+        (
+            PlantType::Spikeweed,
+            "Spike",
+            COST_SPIKEWEED,
+            COLOR_SPIKEWEED_BASE,
+        ),
+        (PlantType::SnowPea, "Snow", COST_SNOWPEA, COLOR_SNOWPEA_HEAD),
+        (
+            PlantType::Torchwood,
+            "Torch",
+            COST_TORCHWOOD,
+            COLOR_TORCHWOOD_FIRE,
+        ),
+        (PlantType::TallNut, "Tall", COST_TALLNUT, COLOR_TALLNUT_BODY),
+        (PlantType::Pumpkin, "Pumpkin", COST_PUMPKIN, COLOR_PUMPKIN),
+        (PlantType::LilyPad, "Lily", COST_LILYPAD, COLOR_LILYPAD),
+        (PlantType::Squash, "Squash", COST_SQUASH, COLOR_SQUASH_BODY),
+        (
+            PlantType::PuffShroom,
+            "Puff",
+            COST_PUFFSHROOM,
+            COLOR_PUFFSHROOM_CAP,
+        ),
+        (
+            PlantType::SunShroom,
+            "SunShr",
+            COST_SUNSHROOM,
+            COLOR_SUNSHROOM_CAP,
+        ),
+        (
+            PlantType::FumeShroom,
+            "Fume",
+            COST_FUMESHROOM,
+            COLOR_FUMESHROOM_CAP,
+        ),
+        (
+            PlantType::ScaredyShroom,
+            "Scaredy",
+            COST_SCAREDYSHROOM,
+            COLOR_SCAREDYSHROOM_CAP,
+        ),
+        (
+            PlantType::CoffeeBean,
+            "Coffee",
+            COST_COFFEEBEAN,
+            COLOR_COFFEEBEAN,
+        ),
+        (
+            PlantType::Starfruit,
+            "Star",
+            COST_STARFRUIT,
+            COLOR_STARFRUIT,
+        ),
+        (PlantType::Cactus, "Cactus", COST_CACTUS, COLOR_CACTUS),
+        (
+            PlantType::Repeater,
+            "Repeat",
+            COST_REPEATER,
+            COLOR_REPEATER_HEAD,
+        ),
+        (PlantType::MelonPult, "Melon", COST_MELONPULT, COLOR_MELON),
+        (
+            PlantType::GatlingPea,
+            "Gatling",
+            COST_GATLINGPEA,
+            COLOR_GATLING_HEAD,
+        ),
+        (
+            PlantType::TwinSunflower,
+            "Twin",
+            COST_TWINSUNFLOWER,
+            COLOR_SUNFLOWER_PETALS,
+        ),
+        (
+            PlantType::WinterMelon,
+            "Winter",
+            COST_WINTERMELON,
+            COLOR_WINTER_MELON,
+        ),
+        (
+            PlantType::MagnetShroom,
+            "Magnet",
+            COST_MAGNETSHROOM,
+            COLOR_MAGNETSHROOM_CAP,
+        ),
+        (PlantType::Garlic, "Garlic", COST_GARLIC, COLOR_GARLIC),
+        (PlantType::Blover, "Blover", COST_BLOVER, COLOR_BLOVER),
+        (
+            PlantType::GraveBuster,
+            "Grave",
+            COST_GRAVEBUSTER,
+            COLOR_GRAVE_BUSTER,
+        ),
+        (
+            PlantType::SplitPea,
+            "Split",
+            COST_SPLITPEA,
+            COLOR_SPLITPEA_HEAD,
+        ),
+        (
+            PlantType::UmbrellaLeaf,
+            "Umbrella",
+            COST_UMBRELLALEAF,
+            COLOR_UMBRELLA_LEAF,
+        ),
     ];
 
     for (i, (ptype, label, cost, color)) in plants.iter().enumerate() {
+        let (left, top) = seed_packet_position(i);
         commands
             .spawn(ButtonBundle {
                 style: Style {
-                    width: Val::Px(80.0),
-                    height: Val::Px(60.0),
+                    width: Val::Px(SEED_PACKET_WIDTH),
+                    height: Val::Px(SEED_PACKET_HEIGHT),
                     position_type: PositionType::Absolute,
-                    right: Val::Px(10.0),
-                    top: Val::Px((i as f32).mul_add(70.0, 50.0)),
+                    left: Val::Px(left),
+                    top: Val::Px(top),
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     flex_direction: FlexDirection::Column,
@@ -107,9 +311,9 @@ pub fn setup(mut commands: Commands) {
                 // Icon preview
                 parent.spawn(NodeBundle {
                     style: Style {
-                        width: Val::Px(20.0),
-                        height: Val::Px(20.0),
-                        margin: UiRect::bottom(Val::Px(5.0)),
+                        width: Val::Px(14.0),
+                        height: Val::Px(14.0),
+                        margin: UiRect::bottom(Val::Px(2.0)),
                         ..default()
                     },
                     background_color: (*color).into(),
@@ -119,25 +323,42 @@ pub fn setup(mut commands: Commands) {
                     TextBundle::from_section(
                         format!("{label}\n{cost}"),
                         TextStyle {
-                            font_size: 16.0,
+                            font_size: 11.0,
                             color: Color::WHITE,
                             ..default()
                         },
                     )
                     .with_text_justify(JustifyText::Center),
                 );
+                // Dark cover that shrinks as the packet recharges
+                parent.spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(0.0),
+                            position_type: PositionType::Absolute,
+                            top: Val::Px(0.0),
+                            left: Val::Px(0.0),
+                            ..default()
+                        },
+                        background_color: COLOR_RECHARGE_OVERLAY.into(),
+                        ..default()
+                    },
+                    RechargeOverlay(*ptype),
+                ));
             });
     }
 
-    // Shovel Button
+    // Shovel Button (next free packet slot)
+    let (shovel_left, shovel_top) = seed_packet_position(plants.len());
     commands
         .spawn(ButtonBundle {
             style: Style {
-                width: Val::Px(80.0),
-                height: Val::Px(60.0),
+                width: Val::Px(SEED_PACKET_WIDTH),
+                height: Val::Px(SEED_PACKET_HEIGHT),
                 position_type: PositionType::Absolute,
-                right: Val::Px(10.0),
-                top: Val::Px((plants.len() as f32).mul_add(70.0, 50.0)),
+                left: Val::Px(shovel_left),
+                top: Val::Px(shovel_top),
                 justify_content: JustifyContent::Center,
                 align_items: AlignItems::Center,
                 flex_direction: FlexDirection::Column,
@@ -153,12 +374,12 @@ pub fn setup(mut commands: Commands) {
                 .spawn(NodeBundle {
                     style: Style {
                         width: Val::Px(30.0),
-                        height: Val::Px(30.0),
+                        height: Val::Px(24.0),
                         display: Display::Flex,
                         flex_direction: FlexDirection::Column,
                         align_items: AlignItems::Center,
                         justify_content: JustifyContent::Center,
-                        margin: UiRect::bottom(Val::Px(5.0)),
+                        margin: UiRect::bottom(Val::Px(2.0)),
                         ..default()
                     },
                     ..default()
@@ -180,7 +401,7 @@ pub fn setup(mut commands: Commands) {
                     icon_parent.spawn(NodeBundle {
                         style: Style {
                             width: Val::Px(4.0),
-                            height: Val::Px(12.0),
+                            height: Val::Px(8.0),
                             ..default()
                         },
                         background_color: Color::rgb(0.4, 0.2, 0.1).into(), // Brown
@@ -190,8 +411,8 @@ pub fn setup(mut commands: Commands) {
                     // Blade
                     icon_parent.spawn(NodeBundle {
                         style: Style {
-                            width: Val::Px(16.0),
-                            height: Val::Px(14.0),
+                            width: Val::Px(14.0),
+                            height: Val::Px(10.0),
                             ..default()
                         },
                         background_color: Color::SILVER.into(),
@@ -203,7 +424,7 @@ pub fn setup(mut commands: Commands) {
                 TextBundle::from_section(
                     "Shovel",
                     TextStyle {
-                        font_size: 14.0,
+                        font_size: 11.0,
                         color: Color::WHITE,
                         ..default()
                     },
@@ -212,3 +433,14 @@ pub fn setup(mut commands: Commands) {
             );
         });
 }
+
+// Seed packets fill the HUD panel right of the lawn, row by row
+fn seed_packet_position(index: usize) -> (f32, f32) {
+    let panel_left = (COLS as f32).mul_add(TILE_SIZE, 60.0);
+    let col = (index % SEED_PACKET_COLUMNS) as f32;
+    let row = (index / SEED_PACKET_COLUMNS) as f32;
+    (
+        col.mul_add(SEED_PACKET_WIDTH + SEED_PACKET_GAP, panel_left),
+        row.mul_add(SEED_PACKET_HEIGHT + SEED_PACKET_GAP, 50.0),
+    )
+}
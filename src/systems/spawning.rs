@@ -1,97 +1,392 @@
-use crate::components::{GridCell, Plant, PlantType, Zombie, ZombieState};
+use crate::components::{
+    Backup, Bungee, BungeePhase, Dancer, Equipment, EquipmentKind, GridCell, HealthBar, ImpCarrier,
+    PartKind, Plant, PlantAnimation, PlantPart, PlantType, Pole, TargetLayer, Tunneling, Vault,
+    Zombie, ZombieAbility, ZombieAnimation, ZombieKind, ZombieLook, ZombiePart, ZombieState,
+};
 use crate::constants::{
-    COLOR_PEASHOOTER_HEAD, COLOR_PEASHOOTER_SNOUT, COLOR_PEASHOOTER_STEM, COLOR_POTATOMINE_ARMED,
-    COLOR_POTATOMINE_BODY, COLOR_SUNFLOWER_FACE, COLOR_SUNFLOWER_PETALS, COLOR_SUNFLOWER_STEM,
-    COLOR_WALLNUT_BODY, COLOR_WALLNUT_FACE, COLOR_ZOMBIE_ARM, COLOR_ZOMBIE_BODY, COLOR_ZOMBIE_HEAD,
-    COLOR_ZOMBIE_LEGS, COLS, ROWS, SCREEN_WIDTH, TILE_SIZE,
+    BUNGEE_MARK_TIME, COLOR_BALLOON, COLOR_BLOVER, COLOR_BUCKET, COLOR_BUNGEE_CORD,
+    COLOR_BUNGEE_TARGET, COLOR_CABBAGE, COLOR_CACTUS, COLOR_COFFEEBEAN, COLOR_CONE,
+    COLOR_DIRT_MOUND, COLOR_FOOTBALL_HELMET, COLOR_FUMESHROOM_CAP, COLOR_GARLIC, COLOR_GARLIC_TIP,
+    COLOR_GATLING_HEAD, COLOR_GATLING_HELMET, COLOR_GRAVE_BUSTER, COLOR_HEALTH_BAR_BACK,
+    COLOR_HEALTH_BAR_FILL, COLOR_IMP_BODY, COLOR_KERNEL, COLOR_LADDER, COLOR_LILYPAD, COLOR_MAGNET,
+    COLOR_MAGNETSHROOM_CAP, COLOR_MELON, COLOR_MUSHROOM_STEM, COLOR_NEWSPAPER,
+    COLOR_PEASHOOTER_HEAD, COLOR_PEASHOOTER_SNOUT, COLOR_PEASHOOTER_STEM, COLOR_PLANT_FLASH,
+    COLOR_POGO, COLOR_POLE, COLOR_POTATOMINE_ARMED, COLOR_POTATOMINE_BODY, COLOR_PUFFSHROOM_CAP,
+    COLOR_PULT_ARM, COLOR_PULT_BASE, COLOR_PUMPKIN, COLOR_REPEATER_HEAD, COLOR_SCAREDYSHROOM_CAP,
+    COLOR_SCREEN_DOOR, COLOR_SNOWPEA_HEAD, COLOR_SNOWPEA_SNOUT, COLOR_SPIKE, COLOR_SPIKEWEED_BASE,
+    COLOR_SPIKEWEED_SPIKE, COLOR_SPLITPEA_HEAD, COLOR_SQUASH_BODY, COLOR_SQUASH_FACE,
+    COLOR_STARFRUIT, COLOR_SUNFLOWER_FACE, COLOR_SUNFLOWER_PETALS, COLOR_SUNFLOWER_STEM,
+    COLOR_SUNSHROOM_CAP, COLOR_TALLNUT_BODY, COLOR_TORCHWOOD_FIRE, COLOR_TORCHWOOD_STUMP,
+    COLOR_UMBRELLA_LEAF, COLOR_WALLNUT_BODY, COLOR_WALLNUT_CRACK, COLOR_WALLNUT_FACE,
+    COLOR_WINTER_MELON, COLOR_ZOMBIE_HEAD, COLOR_ZOMBIE_LEGS, COLOR_ZOMBONI, COLOR_ZOMBONI_ROLLER,
+    COLS, DANCER_PAUSE_TIME, DANCER_RESUMMON_TIME, GRAVE_BUSTER_TIME, GRID_START_X, GRID_START_Y,
+    HEALTH_BAR_HEIGHT, HEALTH_BAR_WIDTH, IMP_THROW_HEIGHT, IMP_THROW_TIME, MAGNET_HOLD_TIME,
+    PLANT_DEPTH, ROWS, SCREEN_HEIGHT, TILE_SIZE, ZOMBIE_DEPTH,
 };
-use crate::resources::SpawnTimer;
+use crate::events::SpawnZombieEvent;
+use crate::resources::{Director, SpawnTimer};
+use crate::systems::animation::row_depth;
 use bevy::prelude::*;
 use rand::Rng;
 
-pub fn spawn_zombies(mut commands: Commands, time: Res<Time>, mut spawn_timer: ResMut<SpawnTimer>) {
+// Every spawn tick is a wave: its point budget grows with the wave count and is spent on a
+// random mix of the kinds it can afford
+pub fn spawn_zombies(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut spawn_timer: ResMut<SpawnTimer>,
+    mut director: ResMut<Director>,
+    plant_query: Query<&GridCell, With<Plant>>,
+) {
     spawn_timer.0.tick(time.delta());
     if spawn_timer.0.finished() {
+        director.wave += 1;
+        let mut budget = 1 + director.wave / 2;
         let mut rng = rand::thread_rng();
-        let row = rng.gen_range(0..ROWS);
 
-        // Match grid calcs from setup
-        // let grid_width_px = COLS as f32 * TILE_SIZE; // Unused
-        let grid_height_px = ROWS as f32 * TILE_SIZE;
-        let start_x = -SCREEN_WIDTH / 2.0 + TILE_SIZE / 2.0 + 50.0;
-        let start_y = -grid_height_px / 2.0 + TILE_SIZE / 2.0;
+        let mut queued = 0.0;
+        while budget > 0 {
+            // Bungees need a plant to go after
+            let cells: Vec<&GridCell> = plant_query.iter().collect();
+            let affordable: Vec<ZombieKind> = ZombieKind::ALL
+                .into_iter()
+                .filter(|kind| kind.stats().cost <= budget)
+                .filter(|kind| kind.stats().ability != ZombieAbility::Drop || !cells.is_empty())
+                .collect();
+            let kind = affordable[rng.gen_range(0..affordable.len())];
+            budget -= kind.stats().cost;
 
-        let x = (COLS as f32).mul_add(TILE_SIZE, start_x) + 40.0; // Start off-screen right
-        let y = (row as f32).mul_add(TILE_SIZE, start_y);
+            if kind.stats().ability == ZombieAbility::Drop {
+                let cell = cells[rng.gen_range(0..cells.len())];
+                spawn_bungee(&mut commands, cell.x, cell.y);
+                continue;
+            }
 
-        let parent = commands
-            .spawn((
-                SpatialBundle {
-                    transform: Transform::from_xyz(x, y, 2.0),
-                    ..default()
-                },
-                Zombie {
-                    health: 100.0,
-                    state: ZombieState::Walking,
-                },
-            ))
-            .id();
+            // Start off-screen right, later ones a little further back
+            let x = (COLS as f32).mul_add(TILE_SIZE, GRID_START_X) + 40.0 + queued;
+            spawn_zombie(&mut commands, kind, x, rng.gen_range(0..ROWS));
+            queued += 30.0;
+        }
+    }
+}
 
-        commands.entity(parent).with_children(|parent| {
-            // Legs
-            parent.spawn(SpriteBundle {
-                sprite: Sprite {
-                    color: COLOR_ZOMBIE_LEGS,
-                    custom_size: Some(Vec2::new(10.0, 30.0)),
-                    ..default()
-                },
-                transform: Transform::from_xyz(-10.0, -30.0, 0.1),
-                ..default()
+// Zombies summoned mid-level by other zombies
+pub fn spawn_zombie_events(mut commands: Commands, mut events: EventReader<SpawnZombieEvent>) {
+    for event in events.read() {
+        let zombie = spawn_zombie(&mut commands, event.kind, event.x, event.row);
+        if let Some((leader, slot)) = event.leader {
+            commands.entity(zombie).insert(Backup { leader, slot });
+        }
+        // Flies in from where it was thrown, `vault_system` keeps it airborne until it lands
+        if let Some(landing) = event.thrown_to {
+            let start = Vec2::new(event.x, (event.row as f32).mul_add(TILE_SIZE, GRID_START_Y));
+            commands.entity(zombie).insert(Vault {
+                start,
+                target: Vec2::new(landing, start.y),
+                height: IMP_THROW_HEIGHT,
+                timer: Timer::from_seconds(IMP_THROW_TIME, TimerMode::Once),
             });
-            parent.spawn(SpriteBundle {
-                sprite: Sprite {
-                    color: COLOR_ZOMBIE_LEGS,
-                    custom_size: Some(Vec2::new(10.0, 30.0)),
-                    ..default()
-                },
-                transform: Transform::from_xyz(10.0, -30.0, 0.1),
+        }
+    }
+}
+
+// Hangs above the screen over its target cell, `bungee_system` takes it from there
+fn spawn_bungee(commands: &mut Commands, col: i32, row: i32) {
+    let x = (col as f32).mul_add(TILE_SIZE, GRID_START_X);
+    let sky_y = SCREEN_HEIGHT / 2.0 + 80.0;
+    let cell_y = (row as f32).mul_add(TILE_SIZE, GRID_START_Y);
+    let zombie = spawn_zombie(commands, ZombieKind::Bungee, x, row);
+    commands
+        .entity(zombie)
+        .insert(Transform::from_xyz(x, sky_y, row_depth(row, ZOMBIE_DEPTH)));
+
+    let marker = commands
+        .spawn(SpriteBundle {
+            sprite: Sprite {
+                color: COLOR_BUNGEE_TARGET,
+                custom_size: Some(Vec2::new(50.0, 20.0)),
                 ..default()
-            });
+            },
+            transform: Transform::from_xyz(0.0, cell_y - sky_y, -0.5),
+            ..default()
+        })
+        .id();
+    let cord = commands
+        .spawn(SpriteBundle {
+            sprite: Sprite {
+                color: COLOR_BUNGEE_CORD,
+                custom_size: Some(Vec2::new(3.0, SCREEN_HEIGHT)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, SCREEN_HEIGHT / 2.0 + 50.0, 0.05),
+            ..default()
+        })
+        .id();
 
-            // Body
-            parent.spawn(SpriteBundle {
+    commands
+        .entity(zombie)
+        .push_children(&[marker, cord])
+        .insert(Bungee {
+            col,
+            phase: BungeePhase::Marking,
+            timer: Timer::from_seconds(BUNGEE_MARK_TIME, TimerMode::Once),
+            marker,
+            sky_y,
+        });
+}
+
+pub fn spawn_zombie(commands: &mut Commands, kind: ZombieKind, x: f32, row: i32) -> Entity {
+    let stats = kind.stats();
+    let y = (row as f32).mul_add(TILE_SIZE, GRID_START_Y);
+
+    let zombie = commands
+        .spawn((
+            SpatialBundle {
+                transform: Transform::from_xyz(x, y, row_depth(row, ZOMBIE_DEPTH))
+                    .with_scale(Vec3::splat(stats.scale)),
+                ..default()
+            },
+            Zombie {
+                kind,
+                health: stats.health,
+                speed: stats.speed,
+                direction: -1.0,
+                layer: stats.layer,
+                state: if stats.ability == ZombieAbility::Drop {
+                    ZombieState::Hanging
+                } else {
+                    ZombieState::Walking
+                },
+                row,
+            },
+            ZombieAnimation::default(),
+        ))
+        .with_children(|parent| {
+            spawn_zombie_visuals(parent, stats.look);
+            spawn_health_bar(parent, 80.0);
+            if let Some(equipment) = stats.equipment {
+                spawn_equipment(parent, equipment);
+            }
+        })
+        .id();
+
+    if stats.layer == TargetLayer::Underground {
+        let mound = commands
+            .spawn(SpriteBundle {
                 sprite: Sprite {
-                    color: COLOR_ZOMBIE_BODY,
-                    custom_size: Some(Vec2::new(30.0, 50.0)),
+                    color: COLOR_DIRT_MOUND,
+                    custom_size: Some(Vec2::new(45.0, 15.0)),
                     ..default()
                 },
-                transform: Transform::from_xyz(0.0, 0.0, 0.2),
+                transform: Transform::from_xyz(0.0, -35.0, 0.5),
                 ..default()
-            });
+            })
+            .id();
+        commands
+            .entity(zombie)
+            .add_child(mound)
+            .insert(Tunneling { mound });
+    }
 
-            // Head
-            parent.spawn(SpriteBundle {
+    if stats.ability == ZombieAbility::ThrowImp {
+        // Imp sitting on its shoulder
+        let imp = commands
+            .spawn(SpriteBundle {
                 sprite: Sprite {
-                    color: COLOR_ZOMBIE_HEAD,
-                    custom_size: Some(Vec2::new(30.0, 30.0)),
+                    color: COLOR_IMP_BODY,
+                    custom_size: Some(Vec2::new(16.0, 24.0)),
                     ..default()
                 },
-                transform: Transform::from_xyz(0.0, 40.0, 0.3),
+                transform: Transform::from_xyz(12.0, 40.0, 0.35),
                 ..default()
-            });
+            })
+            .id();
+        commands
+            .entity(zombie)
+            .add_child(imp)
+            .insert(ImpCarrier { imp: Some(imp) });
+    }
 
-            // Arm
-            parent.spawn(SpriteBundle {
+    if stats.ability == ZombieAbility::Dance {
+        commands.entity(zombie).insert(Dancer {
+            entered: false,
+            pause: Timer::from_seconds(DANCER_PAUSE_TIME, TimerMode::Once),
+            resummon: Timer::from_seconds(DANCER_RESUMMON_TIME, TimerMode::Repeating),
+        });
+    }
+
+    if stats.ability == ZombieAbility::Vault {
+        let sprite = commands
+            .spawn(SpriteBundle {
                 sprite: Sprite {
-                    color: COLOR_ZOMBIE_ARM,
-                    custom_size: Some(Vec2::new(40.0, 10.0)),
+                    color: COLOR_POLE,
+                    custom_size: Some(Vec2::new(70.0, 5.0)),
                     ..default()
                 },
-                transform: Transform::from_xyz(-20.0, 0.0, 0.3),
+                transform: Transform::from_xyz(-25.0, 5.0, 0.35)
+                    .with_rotation(Quat::from_rotation_z(-0.2)),
                 ..default()
-            });
-        });
+            })
+            .id();
+        commands
+            .entity(zombie)
+            .add_child(sprite)
+            .insert(Pole { sprite });
     }
+
+    zombie
+}
+
+fn spawn_zombie_visuals(parent: &mut ChildBuilder, look: ZombieLook) {
+    let ZombieLook::Walker {
+        body: body_color,
+        arm: arm_color,
+    } = look
+    else {
+        spawn_zomboni_visuals(parent);
+        return;
+    };
+
+    // Legs, swinging opposite ways
+    for (x, swing) in [(-10.0, 1.0), (10.0, -1.0)] {
+        spawn_zombie_part(
+            parent,
+            PartKind::Leg(swing),
+            COLOR_ZOMBIE_LEGS,
+            Vec2::new(10.0, 30.0),
+            Vec3::new(x, -30.0, 0.1),
+        );
+    }
+
+    spawn_zombie_part(
+        parent,
+        PartKind::Body,
+        body_color,
+        Vec2::new(30.0, 50.0),
+        Vec3::new(0.0, 0.0, 0.2),
+    );
+    spawn_zombie_part(
+        parent,
+        PartKind::Head,
+        COLOR_ZOMBIE_HEAD,
+        Vec2::new(30.0, 30.0),
+        Vec3::new(0.0, 40.0, 0.3),
+    );
+    spawn_zombie_part(
+        parent,
+        PartKind::Arm,
+        arm_color,
+        Vec2::new(40.0, 10.0),
+        Vec3::new(-20.0, 0.0, 0.3),
+    );
+}
+
+// Ice machine with a zombie driving it, roller at the front
+fn spawn_zomboni_visuals(parent: &mut ChildBuilder) {
+    spawn_zombie_part(
+        parent,
+        PartKind::Body,
+        COLOR_ZOMBONI,
+        Vec2::new(50.0, 35.0),
+        Vec3::new(5.0, -15.0, 0.2),
+    );
+    spawn_zombie_part(
+        parent,
+        PartKind::Body,
+        COLOR_ZOMBONI_ROLLER,
+        Vec2::new(12.0, 20.0),
+        Vec3::new(-25.0, -25.0, 0.3),
+    );
+    // Driver
+    spawn_zombie_part(
+        parent,
+        PartKind::Head,
+        COLOR_ZOMBIE_HEAD,
+        Vec2::new(20.0, 20.0),
+        Vec3::new(12.0, 12.0, 0.3),
+    );
+}
+
+// Sprite the animation systems move around its spawn position
+fn spawn_zombie_part(
+    parent: &mut ChildBuilder,
+    kind: PartKind,
+    color: Color,
+    size: Vec2,
+    base: Vec3,
+) {
+    parent.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color,
+                custom_size: Some(size),
+                ..default()
+            },
+            transform: Transform::from_translation(base),
+            ..default()
+        },
+        ZombiePart { kind, base, color },
+    ));
+}
+
+// Items are their own child entity so they can be detached from the zombie later
+fn spawn_equipment(parent: &mut ChildBuilder, kind: EquipmentKind) {
+    let (color, size, pos) = match kind {
+        EquipmentKind::Cone => (COLOR_CONE, Vec2::new(24.0, 30.0), Vec3::new(0.0, 65.0, 0.4)),
+        EquipmentKind::Bucket => (
+            COLOR_BUCKET,
+            Vec2::new(34.0, 24.0),
+            Vec3::new(0.0, 58.0, 0.4),
+        ),
+        EquipmentKind::FootballHelmet => (
+            COLOR_FOOTBALL_HELMET,
+            Vec2::new(36.0, 22.0),
+            Vec3::new(0.0, 50.0, 0.4),
+        ),
+        // Held in front, covering the body
+        EquipmentKind::ScreenDoor => (
+            COLOR_SCREEN_DOOR,
+            Vec2::new(10.0, 75.0),
+            Vec3::new(-28.0, 0.0, 0.5),
+        ),
+        EquipmentKind::Newspaper => (
+            COLOR_NEWSPAPER,
+            Vec2::new(30.0, 36.0),
+            Vec3::new(-22.0, 5.0, 0.5),
+        ),
+        EquipmentKind::Ladder => (
+            COLOR_LADDER,
+            Vec2::new(16.0, 85.0),
+            Vec3::new(18.0, 5.0, 0.05),
+        ),
+        // Tied above the head
+        EquipmentKind::Balloon => (
+            COLOR_BALLOON,
+            Vec2::new(34.0, 40.0),
+            Vec3::new(0.0, 100.0, 0.05),
+        ),
+        EquipmentKind::PogoStick => (
+            COLOR_POGO,
+            Vec2::new(6.0, 70.0),
+            Vec3::new(-5.0, -25.0, 0.05),
+        ),
+    };
+
+    parent.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color,
+                custom_size: Some(size),
+                ..default()
+            },
+            transform: Transform::from_translation(pos),
+            ..default()
+        },
+        Equipment {
+            kind,
+            health: kind.armor(),
+        },
+    ));
 }
 
 pub fn spawn_plant(
@@ -101,77 +396,248 @@ pub fn spawn_plant(
     y: f32,
     col: i32,
     row: i32,
+    asleep: bool,
 ) {
-    // Default logic
-    let mut health = 100.0;
-    let mut timer = Timer::from_seconds(1.0, TimerMode::Repeating);
+    let (health, timer) = plant_defaults(plant_type);
     let armed = false;
-
-    match plant_type {
-        PlantType::Peashooter => timer = Timer::from_seconds(1.5, TimerMode::Repeating),
-        PlantType::Sunflower => timer = Timer::from_seconds(5.0, TimerMode::Repeating),
-        PlantType::WallNut => health = 1000.0,
-        PlantType::PotatoMine => {
-            timer = Timer::from_seconds(2.0, TimerMode::Once); // Arming time
-            health = 50.0;
-        }
-    }
+    let scale = if plant_type == PlantType::SunShroom {
+        0.6 // Grows up later
+    } else {
+        1.0
+    };
 
     let parent = commands
         .spawn((
             SpatialBundle {
-                transform: Transform::from_xyz(x, y, 1.0),
+                transform: Transform::from_xyz(
+                    x,
+                    y,
+                    row_depth(row, PLANT_DEPTH + plant_type.layer().z()),
+                )
+                .with_scale(Vec3::splat(scale)),
                 ..default()
             },
             Plant {
                 kind: plant_type,
                 timer,
                 health,
+                asleep,
+                age: 0.0,
                 armed,
             },
+            PlantAnimation {
+                clock: 0.0,
+                max_health: health,
+                last_health: health,
+                bite: 0.0,
+                recoil: 0.0,
+            },
             GridCell { x: col, y: row },
         ))
         .id();
 
     commands.entity(parent).with_children(|parent| {
         spawn_plant_visuals(parent, plant_type, 1.0);
+        spawn_health_bar(parent, 45.0);
     });
 }
 
+// Health and action timer a freshly planted plant starts with
+fn plant_defaults(plant_type: PlantType) -> (f32, Timer) {
+    // Default logic
+    let mut health = 100.0;
+    let mut timer = Timer::from_seconds(1.0, TimerMode::Repeating);
+
+    match plant_type {
+        PlantType::Peashooter
+        | PlantType::SnowPea
+        | PlantType::Repeater
+        | PlantType::GatlingPea
+        | PlantType::SplitPea
+        | PlantType::Starfruit
+        | PlantType::Cactus
+        | PlantType::PuffShroom
+        | PlantType::FumeShroom
+        | PlantType::ScaredyShroom => {
+            timer = Timer::from_seconds(1.5, TimerMode::Repeating);
+        }
+        PlantType::SunShroom => timer = Timer::from_seconds(6.0, TimerMode::Repeating),
+        PlantType::MagnetShroom => timer = Timer::from_seconds(MAGNET_HOLD_TIME, TimerMode::Once),
+        PlantType::CoffeeBean => timer = Timer::from_seconds(1.0, TimerMode::Once), // Wake-up time
+        PlantType::Blover => timer = Timer::from_seconds(0.5, TimerMode::Once),     // Gust
+        PlantType::GraveBuster => timer = Timer::from_seconds(GRAVE_BUSTER_TIME, TimerMode::Once),
+        PlantType::Sunflower | PlantType::TwinSunflower => {
+            timer = Timer::from_seconds(5.0, TimerMode::Repeating);
+        }
+        PlantType::WallNut | PlantType::Pumpkin => health = 1000.0,
+        PlantType::Garlic => health = 400.0,
+        PlantType::TallNut => health = 2000.0,
+        PlantType::PotatoMine => {
+            timer = Timer::from_seconds(2.0, TimerMode::Once); // Arming time
+            health = 50.0;
+        }
+        PlantType::CabbagePult
+        | PlantType::KernelPult
+        | PlantType::MelonPult
+        | PlantType::WinterMelon => {
+            timer = Timer::from_seconds(3.0, TimerMode::Repeating);
+        }
+        // Spikeweed: 1s tick between jabs
+        PlantType::Spikeweed
+        | PlantType::Torchwood
+        | PlantType::LilyPad
+        | PlantType::Squash
+        | PlantType::UmbrellaLeaf => {}
+    }
+
+    (health, timer)
+}
+
+// Upgrades replace the base plant in place: same entity, cell, position and health
+pub fn upgrade_plant(commands: &mut Commands, entity: Entity, base: &Plant, upgrade: PlantType) {
+    let (_, timer) = plant_defaults(upgrade);
+    commands
+        .entity(entity)
+        .insert(Plant {
+            kind: upgrade,
+            timer,
+            health: base.health,
+            asleep: false,
+            age: 0.0,
+            armed: false,
+        })
+        .despawn_descendants()
+        .with_children(|parent| {
+            spawn_plant_visuals(parent, upgrade, 1.0);
+            spawn_health_bar(parent, 45.0);
+        });
+}
+
+// Hidden until `health_bar_system` has something to show
+fn spawn_health_bar(parent: &mut ChildBuilder, y: f32) {
+    let mut fill = Entity::PLACEHOLDER;
+    parent
+        .spawn(SpriteBundle {
+            sprite: Sprite {
+                color: COLOR_HEALTH_BAR_BACK,
+                custom_size: Some(Vec2::new(HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, y, 1.0),
+            visibility: Visibility::Hidden,
+            ..default()
+        })
+        .with_children(|bar| {
+            fill = bar
+                .spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: COLOR_HEALTH_BAR_FILL,
+                        custom_size: Some(Vec2::new(HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                    ..default()
+                })
+                .id();
+        })
+        .insert(HealthBar { fill });
+}
+
 pub fn spawn_plant_visuals(parent: &mut ChildBuilder, plant_type: PlantType, alpha: f32) {
+    // Bite flash over the whole plant
+    parent.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: COLOR_PLANT_FLASH,
+                custom_size: Some(Vec2::new(50.0, 60.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, 0.9),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        PlantPart::Flash,
+    ));
+
     match plant_type {
         PlantType::Peashooter => {
-            // Stem
+            spawn_shooter_visuals(parent, COLOR_PEASHOOTER_HEAD, COLOR_PEASHOOTER_SNOUT, alpha);
+        }
+        PlantType::SnowPea => {
+            spawn_shooter_visuals(parent, COLOR_SNOWPEA_HEAD, COLOR_SNOWPEA_SNOUT, alpha);
+        }
+        PlantType::Repeater => {
+            spawn_shooter_visuals(parent, COLOR_REPEATER_HEAD, COLOR_PEASHOOTER_SNOUT, alpha);
+            // Second leaf at the back of the head
             parent.spawn(SpriteBundle {
                 sprite: Sprite {
-                    color: COLOR_PEASHOOTER_STEM.with_a(alpha),
-                    custom_size: Some(Vec2::new(10.0, 40.0)),
+                    color: COLOR_PEASHOOTER_SNOUT.with_a(alpha),
+                    custom_size: Some(Vec2::new(12.0, 10.0)),
                     ..default()
                 },
-                transform: Transform::from_xyz(0.0, -20.0, 0.1),
+                transform: Transform::from_xyz(-18.0, 22.0, 0.1),
                 ..default()
             });
-            // Head
+        }
+        PlantType::SplitPea => {
+            spawn_shooter_visuals(parent, COLOR_SPLITPEA_HEAD, COLOR_PEASHOOTER_SNOUT, alpha);
+            // Second snout facing back
             parent.spawn(SpriteBundle {
                 sprite: Sprite {
-                    color: COLOR_PEASHOOTER_HEAD.with_a(alpha),
-                    custom_size: Some(Vec2::new(30.0, 30.0)),
+                    color: COLOR_PEASHOOTER_SNOUT.with_a(alpha),
+                    custom_size: Some(Vec2::new(20.0, 15.0)),
                     ..default()
                 },
-                transform: Transform::from_xyz(0.0, 15.0, 0.2),
+                transform: Transform::from_xyz(-20.0, 15.0, 0.1),
                 ..default()
             });
-            // Snout
+        }
+        PlantType::GatlingPea => {
+            spawn_shooter_visuals(parent, COLOR_GATLING_HEAD, COLOR_PEASHOOTER_SNOUT, alpha);
+            // Army helmet
             parent.spawn(SpriteBundle {
                 sprite: Sprite {
-                    color: COLOR_PEASHOOTER_SNOUT.with_a(alpha),
-                    custom_size: Some(Vec2::new(20.0, 15.0)),
+                    color: COLOR_GATLING_HELMET.with_a(alpha),
+                    custom_size: Some(Vec2::new(36.0, 12.0)),
                     ..default()
                 },
-                transform: Transform::from_xyz(20.0, 15.0, 0.1),
+                transform: Transform::from_xyz(0.0, 30.0, 0.3),
                 ..default()
             });
         }
+        PlantType::TwinSunflower => {
+            // Stem
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_SUNFLOWER_STEM.with_a(alpha),
+                    custom_size: Some(Vec2::new(10.0, 40.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, -20.0, 0.1),
+                ..default()
+            });
+            // Two smaller heads side by side
+            for x in [-17.0, 17.0] {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: COLOR_SUNFLOWER_PETALS.with_a(alpha),
+                        custom_size: Some(Vec2::new(34.0, 34.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(x, 10.0, 0.2),
+                    ..default()
+                });
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: COLOR_SUNFLOWER_FACE.with_a(alpha),
+                        custom_size: Some(Vec2::new(20.0, 20.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(x, 10.0, 0.3),
+                    ..default()
+                });
+            }
+        }
         PlantType::Sunflower => {
             // Stem
             parent.spawn(SpriteBundle {
@@ -234,29 +700,509 @@ pub fn spawn_plant_visuals(parent: &mut ChildBuilder, plant_type: PlantType, alp
                 transform: Transform::from_xyz(10.0, 10.0, 0.3),
                 ..default()
             });
+            // Cracks, shown as it gets eaten
+            for (stage, x, y, angle) in [(1, -12.0, 18.0, 0.4), (2, 10.0, -12.0, -0.5)] {
+                parent.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: COLOR_WALLNUT_CRACK.with_a(alpha),
+                            custom_size: Some(Vec2::new(4.0, 22.0)),
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(x, y, 0.25)
+                            .with_rotation(Quat::from_rotation_z(angle)),
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    PlantPart::Crack(stage),
+                ));
+            }
         }
         PlantType::PotatoMine => {
-            // Determine if armed color (will act as visual indicator)
-            // Actually, visual update is better in system, but for now just spawn static
+            // Buried until armed, see `plant_visual_system`
+            parent.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: COLOR_POTATOMINE_BODY.with_a(alpha),
+                        custom_size: Some(Vec2::new(30.0, 20.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, -20.0, 0.2),
+                    ..default()
+                },
+                PlantPart::MineBody,
+            ));
+            // Red light, off until armed
+            parent.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: COLOR_POTATOMINE_ARMED.with_a(alpha),
+                        custom_size: Some(Vec2::new(5.0, 5.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, -10.0, 0.3),
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                PlantPart::MineLight,
+            ));
+        }
+        PlantType::CabbagePult => spawn_pult_visuals(parent, COLOR_CABBAGE, alpha),
+        PlantType::KernelPult => spawn_pult_visuals(parent, COLOR_KERNEL, alpha),
+        PlantType::MelonPult => spawn_pult_visuals(parent, COLOR_MELON, alpha),
+        PlantType::WinterMelon => spawn_pult_visuals(parent, COLOR_WINTER_MELON, alpha),
+        PlantType::TallNut => {
+            // Body
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_TALLNUT_BODY.with_a(alpha),
+                    custom_size: Some(Vec2::new(50.0, 80.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 5.0, 0.2),
+                ..default()
+            });
+            // Eyes
+            for x in [-10.0, 10.0] {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: COLOR_WALLNUT_FACE.with_a(alpha),
+                        custom_size: Some(Vec2::new(10.0, 10.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(x, 25.0, 0.3),
+                    ..default()
+                });
+            }
+        }
+        PlantType::Pumpkin => {
+            // Sides and front rim, low enough to keep the plant inside visible
+            for (pos, size) in [
+                (Vec2::new(-30.0, -10.0), Vec2::new(12.0, 45.0)),
+                (Vec2::new(30.0, -10.0), Vec2::new(12.0, 45.0)),
+                (Vec2::new(0.0, -28.0), Vec2::new(70.0, 20.0)),
+            ] {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: COLOR_PUMPKIN.with_a(alpha),
+                        custom_size: Some(size),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(pos.extend(0.1)),
+                    ..default()
+                });
+            }
+        }
+        PlantType::LilyPad => {
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_LILYPAD.with_a(alpha),
+                    custom_size: Some(Vec2::new(70.0, 20.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, -30.0, 0.1),
+                ..default()
+            });
+        }
+        PlantType::PuffShroom => spawn_mushroom_visuals(
+            parent,
+            COLOR_PUFFSHROOM_CAP,
+            Vec2::new(26.0, 16.0),
+            12.0,
+            alpha,
+        ),
+        PlantType::SunShroom => spawn_mushroom_visuals(
+            parent,
+            COLOR_SUNSHROOM_CAP,
+            Vec2::new(40.0, 22.0),
+            16.0,
+            alpha,
+        ),
+        PlantType::FumeShroom => {
+            spawn_mushroom_visuals(
+                parent,
+                COLOR_FUMESHROOM_CAP,
+                Vec2::new(50.0, 28.0),
+                20.0,
+                alpha,
+            );
+            // Nozzle
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_FUMESHROOM_CAP.with_a(alpha),
+                    custom_size: Some(Vec2::new(18.0, 12.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(28.0, -10.0, 0.3),
+                ..default()
+            });
+        }
+        PlantType::ScaredyShroom => spawn_mushroom_visuals(
+            parent,
+            COLOR_SCAREDYSHROOM_CAP,
+            Vec2::new(30.0, 18.0),
+            40.0,
+            alpha,
+        ),
+        PlantType::MagnetShroom => {
+            spawn_mushroom_visuals(
+                parent,
+                COLOR_MAGNETSHROOM_CAP,
+                Vec2::new(44.0, 24.0),
+                18.0,
+                alpha,
+            );
+            // Horseshoe magnet on the cap
+            for x in [-9.0, 9.0] {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: COLOR_MAGNET.with_a(alpha),
+                        custom_size: Some(Vec2::new(7.0, 16.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(x, 12.0, 0.3),
+                    ..default()
+                });
+            }
+        }
+        PlantType::Garlic => {
+            // Bulb
             parent.spawn(SpriteBundle {
                 sprite: Sprite {
-                    color: COLOR_POTATOMINE_BODY.with_a(alpha),
-                    custom_size: Some(Vec2::new(30.0, 20.0)),
+                    color: COLOR_GARLIC.with_a(alpha),
+                    custom_size: Some(Vec2::new(45.0, 40.0)),
                     ..default()
                 },
-                transform: Transform::from_xyz(0.0, -20.0, 0.2),
+                transform: Transform::from_xyz(0.0, -15.0, 0.2),
                 ..default()
             });
-            // Red light (initially off/small)
+            // Tip
             parent.spawn(SpriteBundle {
                 sprite: Sprite {
-                    color: COLOR_POTATOMINE_ARMED.with_a(alpha),
-                    custom_size: Some(Vec2::new(5.0, 5.0)),
+                    color: COLOR_GARLIC_TIP.with_a(alpha),
+                    custom_size: Some(Vec2::new(10.0, 18.0)),
                     ..default()
                 },
-                transform: Transform::from_xyz(0.0, -10.0, 0.3),
+                transform: Transform::from_xyz(0.0, 12.0, 0.1),
                 ..default()
             });
         }
+        PlantType::Blover => {
+            // Stem
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_PEASHOOTER_STEM.with_a(alpha),
+                    custom_size: Some(Vec2::new(8.0, 35.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, -20.0, 0.1),
+                ..default()
+            });
+            // Four clover leaves
+            for (x, y) in [(-12.0, 12.0), (12.0, 12.0), (-12.0, -4.0), (12.0, -4.0)] {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: COLOR_BLOVER.with_a(alpha),
+                        custom_size: Some(Vec2::new(22.0, 18.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(x, y, 0.2),
+                    ..default()
+                });
+            }
+        }
+        PlantType::UmbrellaLeaf => {
+            // Stem
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_PEASHOOTER_STEM.with_a(alpha),
+                    custom_size: Some(Vec2::new(8.0, 45.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, -12.0, 0.1),
+                ..default()
+            });
+            // Wide leaf held overhead
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_UMBRELLA_LEAF.with_a(alpha),
+                    custom_size: Some(Vec2::new(60.0, 16.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 16.0, 0.2),
+                ..default()
+            });
+        }
+        PlantType::GraveBuster => {
+            // Body, low over the headstone
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_GRAVE_BUSTER.with_a(alpha),
+                    custom_size: Some(Vec2::new(55.0, 35.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 5.0, 0.2),
+                ..default()
+            });
+            // Teeth
+            for x in [-15.0, 0.0, 15.0] {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: COLOR_SPIKE.with_a(alpha),
+                        custom_size: Some(Vec2::new(6.0, 8.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(x, -12.0, 0.3),
+                    ..default()
+                });
+            }
+        }
+        PlantType::CoffeeBean => {
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_COFFEEBEAN.with_a(alpha),
+                    custom_size: Some(Vec2::new(20.0, 14.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 30.0, 0.1),
+                ..default()
+            });
+        }
+        PlantType::Starfruit => {
+            // Two overlapping squares make the star
+            for angle in [0.0, std::f32::consts::FRAC_PI_4] {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: COLOR_STARFRUIT.with_a(alpha),
+                        custom_size: Some(Vec2::new(40.0, 40.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, 0.0, 0.2)
+                        .with_rotation(Quat::from_rotation_z(angle)),
+                    ..default()
+                });
+            }
+            // Eyes
+            for x in [-7.0, 7.0] {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: COLOR_WALLNUT_FACE.with_a(alpha),
+                        custom_size: Some(Vec2::new(5.0, 8.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(x, 4.0, 0.3),
+                    ..default()
+                });
+            }
+        }
+        PlantType::Cactus => {
+            // Trunk
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_CACTUS.with_a(alpha),
+                    custom_size: Some(Vec2::new(22.0, 65.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 0.0, 0.2),
+                ..default()
+            });
+            // Arms
+            for (x, y) in [(-16.0, 5.0), (16.0, -5.0)] {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: COLOR_CACTUS.with_a(alpha),
+                        custom_size: Some(Vec2::new(12.0, 25.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(x, y, 0.1),
+                    ..default()
+                });
+            }
+            // Spikes
+            for y in [-15.0, 5.0, 25.0] {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: COLOR_SPIKE.with_a(alpha),
+                        custom_size: Some(Vec2::new(8.0, 3.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(14.0, y, 0.3),
+                    ..default()
+                });
+            }
+        }
+        PlantType::Squash => {
+            // Body
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_SQUASH_BODY.with_a(alpha),
+                    custom_size: Some(Vec2::new(55.0, 55.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, -5.0, 0.2),
+                ..default()
+            });
+            // Angry brow
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_SQUASH_FACE.with_a(alpha),
+                    custom_size: Some(Vec2::new(36.0, 6.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 8.0, 0.3),
+                ..default()
+            });
+        }
+        PlantType::Torchwood => {
+            // Stump
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_TORCHWOOD_STUMP.with_a(alpha),
+                    custom_size: Some(Vec2::new(40.0, 50.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, -10.0, 0.1),
+                ..default()
+            });
+            // Flames
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_TORCHWOOD_FIRE.with_a(alpha),
+                    custom_size: Some(Vec2::new(30.0, 25.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 25.0, 0.2),
+                ..default()
+            });
+        }
+        PlantType::Spikeweed => {
+            // Flat base on the ground
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: COLOR_SPIKEWEED_BASE.with_a(alpha),
+                    custom_size: Some(Vec2::new(60.0, 10.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, -30.0, 0.1),
+                ..default()
+            });
+            // Spikes
+            for x in [-22.0, -8.0, 8.0, 22.0] {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: COLOR_SPIKEWEED_SPIKE.with_a(alpha),
+                        custom_size: Some(Vec2::new(6.0, 6.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(x, -23.0, 0.2)
+                        .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+                    ..default()
+                });
+            }
+        }
     }
 }
+
+// Shared pea shooter body, head colors tell the pea kind apart
+fn spawn_shooter_visuals(
+    parent: &mut ChildBuilder,
+    head_color: Color,
+    snout_color: Color,
+    alpha: f32,
+) {
+    // Stem
+    parent.spawn(SpriteBundle {
+        sprite: Sprite {
+            color: COLOR_PEASHOOTER_STEM.with_a(alpha),
+            custom_size: Some(Vec2::new(10.0, 40.0)),
+            ..default()
+        },
+        transform: Transform::from_xyz(0.0, -20.0, 0.1),
+        ..default()
+    });
+    // Head
+    parent.spawn(SpriteBundle {
+        sprite: Sprite {
+            color: head_color.with_a(alpha),
+            custom_size: Some(Vec2::new(30.0, 30.0)),
+            ..default()
+        },
+        transform: Transform::from_xyz(0.0, 15.0, 0.2),
+        ..default()
+    });
+    // Snout
+    parent.spawn(SpriteBundle {
+        sprite: Sprite {
+            color: snout_color.with_a(alpha),
+            custom_size: Some(Vec2::new(20.0, 15.0)),
+            ..default()
+        },
+        transform: Transform::from_xyz(20.0, 15.0, 0.1),
+        ..default()
+    });
+}
+
+// Stem standing on the ground with a cap on top
+fn spawn_mushroom_visuals(
+    parent: &mut ChildBuilder,
+    cap_color: Color,
+    cap_size: Vec2,
+    stem_height: f32,
+    alpha: f32,
+) {
+    let ground = -35.0;
+    // Stem
+    parent.spawn(SpriteBundle {
+        sprite: Sprite {
+            color: COLOR_MUSHROOM_STEM.with_a(alpha),
+            custom_size: Some(Vec2::new(12.0, stem_height)),
+            ..default()
+        },
+        transform: Transform::from_xyz(0.0, ground + stem_height / 2.0, 0.1),
+        ..default()
+    });
+    // Cap
+    parent.spawn(SpriteBundle {
+        sprite: Sprite {
+            color: cap_color.with_a(alpha),
+            custom_size: Some(cap_size),
+            ..default()
+        },
+        transform: Transform::from_xyz(0.0, ground + stem_height + cap_size.y / 2.0, 0.2),
+        ..default()
+    });
+}
+
+// Shared catapult body, only the ammo in the basket differs
+fn spawn_pult_visuals(parent: &mut ChildBuilder, ammo_color: Color, alpha: f32) {
+    // Base
+    parent.spawn(SpriteBundle {
+        sprite: Sprite {
+            color: COLOR_PULT_BASE.with_a(alpha),
+            custom_size: Some(Vec2::new(40.0, 25.0)),
+            ..default()
+        },
+        transform: Transform::from_xyz(0.0, -25.0, 0.1),
+        ..default()
+    });
+    // Arm
+    parent.spawn(SpriteBundle {
+        sprite: Sprite {
+            color: COLOR_PULT_ARM.with_a(alpha),
+            custom_size: Some(Vec2::new(8.0, 40.0)),
+            ..default()
+        },
+        transform: Transform::from_xyz(-5.0, 0.0, 0.2).with_rotation(Quat::from_rotation_z(0.4)),
+        ..default()
+    });
+    // Basket with ammo
+    parent.spawn(SpriteBundle {
+        sprite: Sprite {
+            color: ammo_color.with_a(alpha),
+            custom_size: Some(Vec2::new(22.0, 20.0)),
+            ..default()
+        },
+        transform: Transform::from_xyz(-15.0, 20.0, 0.3),
+        ..default()
+    });
+}
@@ -1,9 +1,146 @@
-use crate::components::SunText;
-use crate::resources::GameState;
+use crate::components::{
+    DamageNumber, HealthBar, Plant, PlantAnimation, RechargeOverlay, SunText, Zombie,
+};
+use crate::constants::{
+    COLOR_DAMAGE_NUMBER, DAMAGE_NUMBER_DEPTH, DAMAGE_NUMBER_MERGE_TIME, DAMAGE_NUMBER_RISE,
+    DAMAGE_NUMBER_TIME, HEALTH_BAR_WIDTH,
+};
+use crate::events::DamageEvent;
+use crate::resources::{GameState, Settings};
 use bevy::prelude::*;
+use bevy::utils::HashMap;
 
-pub fn ui_system(game_state: Res<GameState>, mut sun_query: Query<&mut Text, With<SunText>>) {
+pub fn ui_system(
+    game_state: Res<GameState>,
+    mut sun_query: Query<&mut Text, With<SunText>>,
+    mut overlay_query: Query<(&RechargeOverlay, &mut Style)>,
+) {
     for mut text in &mut sun_query {
         text.sections[0].value = format!("Sun: {}", game_state.sun);
     }
+
+    for (overlay, mut style) in &mut overlay_query {
+        let remaining = game_state
+            .recharging
+            .get(&overlay.0)
+            .map_or(0.0, |timer| 1.0 - timer.fraction());
+        style.height = Val::Percent(remaining * 100.0);
+    }
+}
+
+// Sizes every health bar to what its plant or zombie has left, bars of anything dead, stolen
+// or unhurt stay hidden
+pub fn health_bar_system(
+    settings: Res<Settings>,
+    mut bar_query: Query<(&HealthBar, &Parent, &mut Visibility)>,
+    mut fill_query: Query<&mut Transform>,
+    plant_query: Query<(&Plant, &PlantAnimation)>,
+    zombie_query: Query<&Zombie>,
+) {
+    for (bar, parent, mut visibility) in &mut bar_query {
+        let fraction = plant_query
+            .get(parent.get())
+            .map(|(plant, animation)| plant.health / animation.max_health)
+            .or_else(|_| {
+                zombie_query
+                    .get(parent.get())
+                    .map(|zombie| zombie.health / zombie.kind.stats().health)
+            })
+            .ok()
+            .filter(|fraction| *fraction < 1.0);
+
+        let Some(fraction) = fraction.filter(|_| settings.show_health) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        *visibility = Visibility::Inherited;
+
+        // Shrinks towards the left end
+        let fraction = fraction.clamp(0.0, 1.0);
+        if let Ok(mut transform) = fill_query.get_mut(bar.fill) {
+            transform.scale.x = fraction;
+            transform.translation.x = -(1.0 - fraction) * HEALTH_BAR_WIDTH / 2.0;
+        }
+    }
+}
+
+// Numbers rise and fade over hit zombies, staying under the fog so they don't give away what's
+// behind it. Explosions hit every frame, so their damage adds up
+// on one number. Runs before `zombie_damage_system` so it sees the health each hit lands on
+pub fn damage_number_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut damage_events: EventReader<DamageEvent>,
+    zombie_query: Query<(&Zombie, &Transform), Without<DamageNumber>>,
+    mut number_query: Query<(Entity, &mut DamageNumber, &mut Transform, &mut Text)>,
+) {
+    // Damage for numbers spawned this frame, and the health each hit zombie has left
+    let mut pending: HashMap<Entity, f32> = HashMap::new();
+    let mut health_left: HashMap<Entity, f32> = HashMap::new();
+    for event in damage_events.read() {
+        if !settings.show_health {
+            continue;
+        }
+
+        // Hits on a zombie that's already dead don't count, same as in `zombie_damage_system`
+        let Ok((zombie, _)) = zombie_query.get(event.zombie) else {
+            continue;
+        };
+        let health = health_left.entry(event.zombie).or_insert(zombie.health);
+        if *health <= 0.0 {
+            continue;
+        }
+        *health -= event.amount;
+
+        if let Some((_, mut number, ..)) = number_query.iter_mut().find(|(_, number, ..)| {
+            number.zombie == event.zombie && number.timer.elapsed_secs() < DAMAGE_NUMBER_MERGE_TIME
+        }) {
+            number.amount += event.amount;
+            continue;
+        }
+        *pending.entry(event.zombie).or_insert(0.0) += event.amount;
+    }
+
+    for (zombie, amount) in pending {
+        let Ok((_, target)) = zombie_query.get(zombie) else {
+            continue;
+        };
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    String::new(),
+                    TextStyle {
+                        font_size: 16.0,
+                        color: COLOR_DAMAGE_NUMBER,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_xyz(
+                    target.translation.x,
+                    target.translation.y + 70.0,
+                    DAMAGE_NUMBER_DEPTH,
+                ),
+                ..default()
+            },
+            DamageNumber {
+                zombie,
+                amount,
+                timer: Timer::from_seconds(DAMAGE_NUMBER_TIME, TimerMode::Once),
+            },
+        ));
+    }
+
+    for (entity, mut number, mut transform, mut text) in &mut number_query {
+        number.timer.tick(time.delta());
+        transform.translation.y += DAMAGE_NUMBER_RISE * time.delta_seconds();
+        text.sections[0].value = format!("{:.0}", number.amount);
+        text.sections[0]
+            .style
+            .color
+            .set_a(1.0 - number.timer.fraction());
+        if number.timer.finished() || !settings.show_health {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
 }